@@ -1,7 +1,12 @@
 #!/usr/bin/env python3
 """
 Download TFT champion data and icons from Riot Data Dragon.
-Creates data/champions.json and data/templates/champions/*.png
+Creates data/champions.json and data/templates/champions/*.png,
+plus data/augments.json and data/templates/augments/*.png.
+Traits (and each champion's trait list and attack range) come from Community Dragon, since
+Data Dragon's champion data does not include them: data/traits.json and
+data/templates/traits/*.png. Items and their recipes also come from Community
+Dragon: data/items.json and data/templates/items/*.png.
 
 Usage: python3 scripts/fetch-templates.py [--set SET_NUMBER]
 """
@@ -13,9 +18,18 @@ import sys
 import urllib.request
 
 DATA_DRAGON_BASE = "https://ddragon.leagueoflegends.com"
+CDRAGON_BASE = "https://raw.communitydragon.org/latest"
 PROJECT_ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
 CHAMPIONS_JSON = os.path.join(PROJECT_ROOT, "data", "champions.json")
 TEMPLATES_DIR = os.path.join(PROJECT_ROOT, "data", "templates", "champions")
+AUGMENTS_JSON = os.path.join(PROJECT_ROOT, "data", "augments.json")
+AUGMENT_TEMPLATES_DIR = os.path.join(PROJECT_ROOT, "data", "templates", "augments")
+TRAITS_JSON = os.path.join(PROJECT_ROOT, "data", "traits.json")
+TRAIT_TEMPLATES_DIR = os.path.join(PROJECT_ROOT, "data", "templates", "traits")
+ITEMS_JSON = os.path.join(PROJECT_ROOT, "data", "items.json")
+ITEM_TEMPLATES_DIR = os.path.join(PROJECT_ROOT, "data", "templates", "items")
+
+_cdragon_cache = {}
 
 
 def get_latest_version():
@@ -32,6 +46,51 @@ def fetch_tft_champions(version):
     return data.get("data", {})
 
 
+def fetch_tft_augments(version):
+    url = f"{DATA_DRAGON_BASE}/cdn/{version}/data/en_US/tft-augments.json"
+    with urllib.request.urlopen(url) as resp:
+        data = json.loads(resp.read())
+    return data.get("data", {})
+
+
+def fetch_cdragon():
+    """Return the full Community Dragon TFT data (fetched once), or None."""
+    if "data" not in _cdragon_cache:
+        url = f"{CDRAGON_BASE}/cdragon/tft/en_us.json"
+        try:
+            with urllib.request.urlopen(url) as resp:
+                _cdragon_cache["data"] = json.loads(resp.read())
+        except Exception as e:
+            print(f"  WARN: Failed to fetch {url}: {e}", file=sys.stderr)
+            _cdragon_cache["data"] = None
+    return _cdragon_cache["data"]
+
+
+def fetch_cdragon_set(target_set):
+    """Return the Community Dragon setData entry for the given set, or None."""
+    data = fetch_cdragon()
+    if data is None:
+        return None
+    candidates = [s for s in data.get("setData", []) if s.get("number") == target_set]
+    # The plain set mutator (e.g. "TFTSet16") carries the full champion list
+    candidates.sort(key=lambda s: s.get("mutator") != f"TFTSet{target_set}")
+    return candidates[0] if candidates else None
+
+
+def cdragon_asset_url(path):
+    """Convert a game asset path like ASSETS/UX/Foo.tex to its PNG URL."""
+    return f"{CDRAGON_BASE}/game/{path.lower().rsplit('.', 1)[0]}.png"
+
+
+def augment_tier(name):
+    """Data Dragon has no rarity field; tiered augments carry a roman numeral suffix."""
+    if name.endswith(" III"):
+        return "prismatic"
+    if name.endswith(" II"):
+        return "gold"
+    return "silver"
+
+
 def detect_current_set(raw_champions):
     """Find the highest TFT set number in the data."""
     max_set = 0
@@ -82,6 +141,38 @@ def main():
         shutil.rmtree(TEMPLATES_DIR)
     os.makedirs(TEMPLATES_DIR, exist_ok=True)
 
+    print("Fetching trait data from Community Dragon...")
+    cdragon_set = fetch_cdragon_set(target_set)
+    champion_traits = {}
+    champion_ranges = {}
+    traits = []
+    trait_icons = 0
+    if cdragon_set:
+        trait_ids = {t["name"]: t["apiName"] for t in cdragon_set.get("traits", [])}
+        for champ in cdragon_set.get("champions", []):
+            champion_traits[champ.get("apiName")] = [
+                trait_ids[name] for name in champ.get("traits", []) if name in trait_ids
+            ]
+            champion_ranges[champ.get("apiName")] = int(champ.get("stats", {}).get("range") or 0)
+
+        if os.path.exists(TRAIT_TEMPLATES_DIR):
+            shutil.rmtree(TRAIT_TEMPLATES_DIR)
+        os.makedirs(TRAIT_TEMPLATES_DIR, exist_ok=True)
+
+        for t in cdragon_set.get("traits", []):
+            breakpoints = sorted({e["minUnits"] for e in t.get("effects", []) if e.get("minUnits")})
+            traits.append({
+                "id": t["apiName"],
+                "name": t["name"],
+                "breakpoints": breakpoints,
+                "icon": f"{t['apiName']}.png",
+            })
+            if t.get("icon") and download_icon(
+                cdragon_asset_url(t["icon"]),
+                os.path.join(TRAIT_TEMPLATES_DIR, f"{t['apiName']}.png"),
+            ):
+                trait_icons += 1
+
     champions = []
     downloaded = 0
 
@@ -92,7 +183,6 @@ def main():
 
         name = champ_data.get("name", champ_id)
         cost = champ_data.get("tier", 1)
-        traits = [t.get("name", t.get("id", "")) for t in champ_data.get("traits", [])]
         icon_dd_file = champ_data.get("image", {}).get("full", f"{champ_id}.png")
 
         # Extract short ID (e.g. TFT15_Ahri from the full path)
@@ -103,8 +193,9 @@ def main():
             "id": short_id,
             "name": name,
             "cost": cost,
-            "traits": traits,
+            "traits": champion_traits.get(short_id, []),
             "icon": f"{short_id}.png",
+            "range": champion_ranges.get(short_id, 0),
         })
 
         # Download icon
@@ -123,9 +214,85 @@ def main():
     with open(CHAMPIONS_JSON, "w") as f:
         json.dump(output, f, indent=2)
 
+    with open(TRAITS_JSON, "w") as f:
+        json.dump({"version": version, "set": target_set, "traits": traits}, f, indent=2)
+
+    print("Fetching TFT augment data...")
+    raw_augments = fetch_tft_augments(version)
+
+    if os.path.exists(AUGMENT_TEMPLATES_DIR):
+        shutil.rmtree(AUGMENT_TEMPLATES_DIR)
+    os.makedirs(AUGMENT_TEMPLATES_DIR, exist_ok=True)
+
+    augments = []
+    augment_icons = 0
+    for augment_id, augment_data in sorted(raw_augments.items()):
+        if set_prefix not in augment_id and short_prefix not in augment_id \
+                and "TFT_Augment" not in augment_id:
+            continue
+
+        name = augment_data.get("name", augment_id).strip()
+        icon_dd_file = augment_data.get("image", {}).get("full", f"{augment_id}.png")
+        short_id = augment_id.split("/")[-1]
+
+        augments.append({
+            "id": short_id,
+            "name": name,
+            "tier": augment_tier(name),
+            "description": augment_data.get("desc", ""),
+            "icon": f"{short_id}.png",
+        })
+
+        url = f"{DATA_DRAGON_BASE}/cdn/{version}/img/tft-augment/{icon_dd_file}"
+        path = os.path.join(AUGMENT_TEMPLATES_DIR, f"{short_id}.png")
+        if download_icon(url, path):
+            augment_icons += 1
+
+    with open(AUGMENTS_JSON, "w") as f:
+        json.dump({"version": version, "set": target_set, "augments": augments}, f, indent=2)
+
+    print("Fetching item data from Community Dragon...")
+    items = []
+    item_icons = 0
+    cdragon = fetch_cdragon()
+    if cdragon:
+        # Set-specific items such as trait emblems are prefixed TFT16_Item_
+        raw_items = [i for i in cdragon.get("items", [])
+                     if re.match(rf"TFT({target_set})?_Item_", i.get("apiName") or "")]
+        used_in_recipes = {c for i in raw_items for c in i.get("composition", [])}
+
+        if os.path.exists(ITEM_TEMPLATES_DIR):
+            shutil.rmtree(ITEM_TEMPLATES_DIR)
+        os.makedirs(ITEM_TEMPLATES_DIR, exist_ok=True)
+
+        for item in sorted(raw_items, key=lambda i: i["apiName"]):
+            api_name = item["apiName"]
+            composition = item.get("composition", [])
+            is_component = not composition and api_name in used_in_recipes
+            if not is_component and len(composition) != 2:
+                continue
+            items.append({
+                "id": api_name,
+                "name": (item.get("name") or api_name).strip(),
+                "is_component": is_component,
+                "recipe": composition if len(composition) == 2 else None,
+                "icon": f"{api_name}.png",
+            })
+            if item.get("icon") and download_icon(
+                cdragon_asset_url(item["icon"]),
+                os.path.join(ITEM_TEMPLATES_DIR, f"{api_name}.png"),
+            ):
+                item_icons += 1
+
+    with open(ITEMS_JSON, "w") as f:
+        json.dump({"version": version, "set": target_set, "items": items}, f, indent=2)
+
     print(f"\nDone!")
     print(f"  Set {target_set}: {len(champions)} champions saved to {CHAMPIONS_JSON}")
     print(f"  Icons: {downloaded} downloaded to {TEMPLATES_DIR}")
+    print(f"  Traits: {len(traits)} saved to {TRAITS_JSON}, {trait_icons} icons")
+    print(f"  Augments: {len(augments)} saved to {AUGMENTS_JSON}, {augment_icons} icons")
+    print(f"  Items: {len(items)} saved to {ITEMS_JSON}, {item_icons} icons")
 
 
 if __name__ == "__main__":
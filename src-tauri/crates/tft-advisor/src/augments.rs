@@ -0,0 +1,279 @@
+use crate::comp::{held_units, unit_matches};
+use crate::{AdviceTrace, AugmentAdvice};
+use tft_data::{AugmentTag, GameData, MetaComp};
+use tft_state::{parse_stage, GameState};
+
+/// Base score of each tier list tier
+const TIER_SCORES: [(&str, f64); 4] = [("S", 1.0), ("A", 0.75), ("B", 0.5), ("C", 0.25)];
+/// Base score of an augment the tier list does not rate
+const UNRATED_SCORE: f64 = 0.4;
+
+/// Bonus for an augment the tier list marks as best in the targeted comp
+const COMP_BONUS: f64 = 0.3;
+
+/// Econ augments pay off over many rounds: worth more up to stage 2, less
+/// from stage 4, and more when short on gold
+const EARLY_STAGE: u32 = 2;
+const LATE_STAGE: u32 = 4;
+const ECON_BONUS: f64 = 0.15;
+const POOR_GOLD: u32 = 20;
+const POOR_BONUS: f64 = 0.1;
+
+/// Health augments at or below this HP
+const LOW_HP: u32 = 40;
+const HEALTH_BONUS: f64 = 0.2;
+
+/// Item augments once a unit the comp builds items on is held
+const ITEM_BONUS: f64 = 0.15;
+
+/// Level augments up to this stage, before the push to 8
+const LEVEL_STAGE: u32 = 3;
+const LEVEL_BONUS: f64 = 0.1;
+
+/// Rank the augments on the selection screen, best first: their tier list
+/// score, adjusted for the comp being played, the stage, gold, HP and held
+/// item carriers. Cards that were not recognised are left out.
+pub fn augment_advice(
+    state: &GameState,
+    data: &GameData,
+    comp: Option<&MetaComp>,
+) -> Vec<AugmentAdvice> {
+    let stage = parse_stage(&state.stage)
+        .map(|(stage, _)| stage)
+        .unwrap_or(0);
+    // HP reads 0 until the player list was recognised
+    let hp = (state.hp > 0).then_some(state.hp);
+    let carrier = comp.and_then(|comp| {
+        held_units(state)
+            .into_iter()
+            .find(|c| comp.core_items.keys().any(|unit| unit_matches(unit, c)))
+    });
+
+    let mut advice: Vec<AugmentAdvice> = state
+        .augment_offer
+        .iter()
+        .filter_map(|card| {
+            let id = card.augment_id.clone().or_else(|| {
+                let name = card.name.as_deref()?;
+                Some(data.augment_by_name(name)?.id.clone())
+            })?;
+            let name = data
+                .augments
+                .get(&id)
+                .map(|a| a.name.clone())
+                .or_else(|| card.name.clone())
+                .unwrap_or_else(|| id.clone());
+            let rating = data.augment_ratings.get(&id);
+
+            let mut trace = AdviceTrace::new("augments.rank")
+                .input("augment", id.as_str())
+                .input("tier", rating.map(|r| r.tier.as_str()))
+                .input("stage", state.stage.as_str())
+                .input("gold", state.gold)
+                .input("hp", hp)
+                .input("comp", comp.map(|c| c.name.as_str()));
+            let mut reasons = Vec::new();
+
+            let mut score = match rating {
+                Some(r) => {
+                    reasons.push(format!("{}-tier augment", r.tier));
+                    TIER_SCORES
+                        .iter()
+                        .find(|(tier, _)| tier.eq_ignore_ascii_case(&r.tier))
+                        .map_or(UNRATED_SCORE, |(_, score)| *score)
+                }
+                None => {
+                    reasons.push("Not on the tier list".to_string());
+                    UNRATED_SCORE
+                }
+            };
+
+            let tags = rating.map(|r| r.tags.as_slice()).unwrap_or_default();
+            if let (Some(r), Some(comp)) = (rating, comp) {
+                if trace.check("comp", "in", r.comps.clone(), r.comps.contains(&comp.name)) {
+                    score += COMP_BONUS;
+                    reasons.push(format!("Strong in {}", comp.name));
+                }
+            }
+            if tags.contains(&AugmentTag::Econ) {
+                if trace.check("stage", "<=", EARLY_STAGE, stage <= EARLY_STAGE) {
+                    score += ECON_BONUS;
+                    reasons.push("Early econ compounds over the game".to_string());
+                } else if trace.check("stage", ">=", LATE_STAGE, stage >= LATE_STAGE) {
+                    score -= ECON_BONUS;
+                    reasons.push("Too late for econ to pay off".to_string());
+                }
+                if trace.check("gold", "<", POOR_GOLD, state.gold < POOR_GOLD) {
+                    score += POOR_BONUS;
+                    reasons.push(format!("Only {} gold", state.gold));
+                }
+            }
+            if tags.contains(&AugmentTag::Health) {
+                if let Some(hp) = hp {
+                    if trace.check("hp", "<=", LOW_HP, hp <= LOW_HP) {
+                        score += HEALTH_BONUS;
+                        reasons.push(format!("{} HP left", hp));
+                    }
+                }
+            }
+            if tags.contains(&AugmentTag::Items) {
+                if let Some(carrier) = carrier {
+                    score += ITEM_BONUS;
+                    reasons.push(format!("Items for {}", carrier.name.trim()));
+                }
+            }
+            if tags.contains(&AugmentTag::Level)
+                && trace.check("stage", "<=", LEVEL_STAGE, stage <= LEVEL_STAGE)
+            {
+                score += LEVEL_BONUS;
+                reasons.push("Levels up early for tempo".to_string());
+            }
+
+            Some(AugmentAdvice {
+                slot: card.slot,
+                augment_id: id,
+                name,
+                score,
+                reasons,
+                trace,
+            })
+        })
+        .collect();
+
+    advice.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.slot.cmp(&b.slot)));
+    let scores: Vec<(String, f64)> = advice
+        .iter()
+        .map(|a| (a.augment_id.clone(), a.score))
+        .collect();
+    for a in &mut advice {
+        for (id, score) in scores.iter().filter(|(id, _)| *id != a.augment_id) {
+            a.trace.alternative(id, *score);
+        }
+    }
+    advice
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tft_data::{AugmentData, AugmentRating, AugmentTier};
+    use tft_state::{BoardSlot, Champion, OfferedAugment};
+
+    fn data() -> GameData {
+        let mut data = GameData::default();
+        for (id, tier, tag) in [
+            ("Econ", "A", AugmentTag::Econ),
+            ("Items", "B", AugmentTag::Items),
+            ("Health", "B", AugmentTag::Health),
+        ] {
+            data.augments.insert(
+                id.to_string(),
+                AugmentData {
+                    id: id.to_string(),
+                    name: format!("{} Augment", id),
+                    tier: AugmentTier::Gold,
+                    description: String::new(),
+                    icon: String::new(),
+                },
+            );
+            data.augments_by_name
+                .insert(format!("{} augment", id.to_lowercase()), id.to_string());
+            data.augment_ratings.insert(
+                id.to_string(),
+                AugmentRating {
+                    id: id.to_string(),
+                    tier: tier.to_string(),
+                    tags: vec![tag],
+                    comps: if id == "Items" {
+                        vec!["Jinx Reroll".to_string()]
+                    } else {
+                        Vec::new()
+                    },
+                },
+            );
+        }
+        data
+    }
+
+    fn offer(stage: &str, gold: u32, hp: u32) -> GameState {
+        let card = |slot: usize, id: Option<&str>, name: Option<&str>| OfferedAugment {
+            slot,
+            augment_id: id.map(String::from),
+            name: name.map(String::from),
+            highlighted: false,
+        };
+        GameState {
+            stage: stage.to_string(),
+            gold,
+            hp,
+            augment_offer: vec![
+                card(0, Some("Items"), None),
+                // Recognised by name only
+                card(1, None, Some("Econ Augment")),
+                card(2, Some("Health"), None),
+                card(3, None, None),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_early_econ() {
+        let advice = augment_advice(&offer("2-1", 12, 90), &data(), None);
+        assert_eq!(advice.len(), 3);
+        assert_eq!(advice[0].augment_id, "Econ");
+        assert_eq!(advice[0].slot, 1);
+        assert_eq!(advice[0].name, "Econ Augment");
+        assert!((advice[0].score - 1.0).abs() < 1e-9);
+        assert_eq!(
+            advice[0].reasons,
+            vec![
+                "A-tier augment",
+                "Early econ compounds over the game",
+                "Only 12 gold"
+            ]
+        );
+        assert_eq!(advice[0].trace.alternatives.len(), 2);
+        // Equal scores keep the slot order
+        assert_eq!(advice[1].augment_id, "Items");
+        assert_eq!(advice[2].augment_id, "Health");
+    }
+
+    #[test]
+    fn test_fits_board_and_hp() {
+        let comp = MetaComp {
+            name: "Jinx Reroll".to_string(),
+            tier: "S".to_string(),
+            core_units: vec!["Jinx".to_string()],
+            flex_units: Vec::new(),
+            core_items: HashMap::from([("Jinx".to_string(), Vec::new())]),
+            early_game: Vec::new(),
+            power_spike: "3-2".to_string(),
+        };
+        let mut state = offer("4-2", 40, 30);
+        state.board = vec![BoardSlot {
+            row: 3,
+            col: 0,
+            champion: Some(Champion {
+                id: "Jinx".to_string(),
+                name: "Jinx".to_string(),
+                cost: 2,
+                traits: Vec::new(),
+            }),
+            star_level: 2,
+            items: Vec::new(),
+        }];
+
+        let advice = augment_advice(&state, &data(), Some(&comp));
+        let order: Vec<&str> = advice.iter().map(|a| a.augment_id.as_str()).collect();
+        assert_eq!(order, vec!["Items", "Health", "Econ"]);
+        assert!(advice[0]
+            .reasons
+            .contains(&"Strong in Jinx Reroll".to_string()));
+        assert!(advice[0].reasons.contains(&"Items for Jinx".to_string()));
+        assert!(advice[2]
+            .reasons
+            .contains(&"Too late for econ to pay off".to_string()));
+    }
+}
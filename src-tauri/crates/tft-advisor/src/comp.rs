@@ -0,0 +1,199 @@
+use crate::{AdviceTrace, CompAdvice};
+use tft_data::MetaComp;
+use tft_state::{Champion, GameState};
+
+/// Share of a unit's worth counted when it is only in the shop
+const SHOP_WEIGHT: f64 = 0.5;
+
+/// Share of a core unit's worth counted for a flex unit
+const FLEX_WEIGHT: f64 = 0.5;
+
+/// Runner-up comps listed in a comp's trace
+const TRACE_ALTERNATIVES: usize = 3;
+
+/// Whether a comp's unit entry, an ID or a display name, names this champion
+pub(crate) fn unit_matches(entry: &str, champion: &Champion) -> bool {
+    entry == champion.id || entry.trim().eq_ignore_ascii_case(champion.name.trim())
+}
+
+/// Champions on the bench and board, one entry per unit
+pub(crate) fn held_units(state: &GameState) -> Vec<&Champion> {
+    state
+        .bench
+        .iter()
+        .chain(&state.board)
+        .filter_map(|slot| slot.champion.as_ref())
+        .collect()
+}
+
+/// How well the units match a comp, 0-1: the share of core units held, flex
+/// units worth half a core unit and units only in the shop worth half again
+pub fn comp_score(comp: &MetaComp, held: &[&Champion], shop: &[&Champion]) -> f64 {
+    let worth = |entry: &String| {
+        if held.iter().any(|c| unit_matches(entry, c)) {
+            1.0
+        } else if shop.iter().any(|c| unit_matches(entry, c)) {
+            SHOP_WEIGHT
+        } else {
+            0.0
+        }
+    };
+    let core: f64 = comp.core_units.iter().map(worth).sum();
+    let flex: f64 = comp.flex_units.iter().map(worth).sum();
+    let max = comp.core_units.len() as f64 + comp.flex_units.len() as f64 * FLEX_WEIGHT;
+    if max == 0.0 {
+        return 0.0;
+    }
+    (core + flex * FLEX_WEIGHT) / max
+}
+
+/// The `n` comps closest to the board, bench and shop, best first. Comps
+/// with nothing in common are left out.
+pub fn comp_advice(comps: &[MetaComp], state: &GameState, n: usize) -> Vec<CompAdvice> {
+    let held = held_units(state);
+    let ranked = ranked(comps, state);
+    let shop_units = state.shop.iter().filter(|s| s.champion.is_some()).count();
+    ranked
+        .iter()
+        .take(n)
+        .map(|&(comp, score)| {
+            let missing_units = comp
+                .core_units
+                .iter()
+                .filter(|u| !held.iter().any(|c| unit_matches(u, c)))
+                .cloned()
+                .collect();
+
+            // Items for core units first, in comp order
+            let mut core_items: Vec<String> = Vec::new();
+            let mut carriers: Vec<&String> = comp.core_items.keys().collect();
+            carriers.sort_by_key(|unit| {
+                let position = comp.core_units.iter().position(|u| u == *unit);
+                (position.unwrap_or(usize::MAX), *unit)
+            });
+            for unit in carriers {
+                for item in &comp.core_items[unit] {
+                    if !core_items.contains(item) {
+                        core_items.push(item.clone());
+                    }
+                }
+            }
+
+            let mut trace = AdviceTrace::new("comp.match")
+                .input("heldUnits", held.len())
+                .input("shopUnits", shop_units);
+            for (other, other_score) in ranked
+                .iter()
+                .filter(|(other, _)| other.name != comp.name)
+                .take(TRACE_ALTERNATIVES)
+            {
+                trace.alternative(&other.name, *other_score);
+            }
+
+            CompAdvice {
+                comp_name: comp.name.clone(),
+                match_score: score,
+                missing_units,
+                core_items,
+                trace,
+            }
+        })
+        .collect()
+}
+
+/// The comp the units come closest to, if they match any at all
+pub fn best_comp<'a>(comps: &'a [MetaComp], state: &GameState) -> Option<&'a MetaComp> {
+    ranked(comps, state).first().map(|(comp, _)| *comp)
+}
+
+/// Comps scoring above zero, best first
+fn ranked<'a>(comps: &'a [MetaComp], state: &GameState) -> Vec<(&'a MetaComp, f64)> {
+    let held = held_units(state);
+    let shop: Vec<&Champion> = state
+        .shop
+        .iter()
+        .filter_map(|s| s.champion.as_ref())
+        .collect();
+    let mut ranked: Vec<_> = comps
+        .iter()
+        .map(|comp| (comp, comp_score(comp, &held, &shop)))
+        .filter(|(_, score)| *score > 0.0)
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tft_state::{BoardSlot, ShopSlot};
+
+    fn champion(id: &str) -> Champion {
+        Champion {
+            id: id.to_string(),
+            name: id.to_string(),
+            cost: 2,
+            traits: Vec::new(),
+        }
+    }
+
+    fn comp(name: &str, core: &[&str], flex: &[&str]) -> MetaComp {
+        let ids = |list: &[&str]| list.iter().map(|s| s.to_string()).collect();
+        MetaComp {
+            name: name.to_string(),
+            tier: "A".to_string(),
+            core_units: ids(core),
+            flex_units: ids(flex),
+            core_items: HashMap::from([
+                ("Vi".to_string(), vec!["Bloodthirster".to_string()]),
+                (
+                    "Jinx".to_string(),
+                    vec!["Deathblade".to_string(), "Bloodthirster".to_string()],
+                ),
+            ]),
+            early_game: Vec::new(),
+            power_spike: "3-2".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_comp_advice_ranks_and_fills() {
+        let comps = vec![
+            comp("Jinx Reroll", &["Jinx", "Vi", "Ekko"], &["Poppy"]),
+            comp("Zed Fast 9", &["Zed", "Ahri"], &[]),
+            comp("Nothing", &["Lux"], &[]),
+        ];
+        let state = GameState {
+            board: vec![BoardSlot {
+                row: 0,
+                col: 0,
+                champion: Some(champion("Jinx")),
+                star_level: 1,
+                items: Vec::new(),
+            }],
+            shop: vec![ShopSlot {
+                index: 0,
+                champion: Some(champion("Zed")),
+                confidence: 0.9,
+            }],
+            ..Default::default()
+        };
+
+        let advice = comp_advice(&comps, &state, 5);
+        assert_eq!(advice.len(), 2);
+        assert_eq!(advice[0].comp_name, "Jinx Reroll");
+        // 1 core held out of 3 core + half a flex
+        assert!((advice[0].match_score - 1.0 / 3.5).abs() < 1e-9);
+        assert_eq!(advice[0].missing_units, vec!["Vi", "Ekko"]);
+        assert_eq!(advice[0].core_items, vec!["Deathblade", "Bloodthirster"]);
+        // A shop unit counts half
+        assert!((advice[1].match_score - 0.25).abs() < 1e-9);
+
+        // Even when only the best comp is shown, its trace names the runner-up
+        let best = comp_advice(&comps, &state, 1);
+        assert_eq!(best.len(), 1);
+        assert_eq!(best[0].trace.alternatives[0].option, "Zed Fast 9");
+        assert_eq!(best_comp(&comps, &state).unwrap().name, "Jinx Reroll");
+    }
+}
@@ -0,0 +1,254 @@
+use crate::{AdviceTrace, EconAction, EconAdvice};
+use tft_state::{parse_stage, GameState};
+
+/// Gold amounts at which interest goes up by one
+const INTEREST_BREAKPOINTS: [u32; 5] = [10, 20, 30, 40, 50];
+
+/// Gold cost of one XP purchase
+const XP_COST: u32 = 4;
+
+/// Standard leveling curve: from (stage, round) on, be at least this level
+const LEVEL_TIMINGS: [((u32, u32), u32); 5] = [
+    ((2, 1), 4),
+    ((2, 5), 5),
+    ((3, 2), 6),
+    ((4, 1), 7),
+    ((5, 1), 8),
+];
+
+/// Going to 8 a stage early at 4-2 needs a healthy econ or HP to spare
+const FAST_8_STAGE: (u32, u32) = (4, 2);
+const FAST_8_GOLD: u32 = 50;
+const FAST_8_HP: u32 = 60;
+
+/// Roll everything at or below this HP
+const CRITICAL_HP: u32 = 20;
+/// Roll down to the first breakpoint at or below this HP
+const LOW_HP: u32 = 40;
+/// A loss streak this long with HP at or below `STREAK_HP` breaks the streak
+const LOSS_STREAK: i32 = 3;
+const STREAK_HP: u32 = 50;
+
+/// Economy advice from gold, level, stage, HP and streak. `None` until the
+/// HUD has been read.
+pub fn econ_advice(state: &GameState) -> Option<EconAdvice> {
+    let stage = parse_stage(&state.stage)?;
+    if state.level == 0 {
+        return None;
+    }
+    // HP reads 0 until the player list was recognised
+    let hp = (state.hp > 0).then_some(state.hp);
+    let gold = state.gold;
+    let mut trace = AdviceTrace::default()
+        .input("gold", gold)
+        .input("level", state.level)
+        .input("hp", hp)
+        .input("stage", state.stage.as_str())
+        .input("streak", state.streak);
+
+    if let Some(hp) = hp {
+        if trace.check("hp", "<=", CRITICAL_HP, hp <= CRITICAL_HP) {
+            return Some(advice(
+                EconAction::Roll,
+                format!("{} HP left: roll everything to stabilise", hp),
+                0,
+                trace.fired("econ.critical-hp"),
+            ));
+        }
+        if trace.check("hp", "<=", LOW_HP, hp <= LOW_HP)
+            && trace.check(
+                "gold",
+                ">",
+                INTEREST_BREAKPOINTS[0],
+                gold > INTEREST_BREAKPOINTS[0],
+            )
+        {
+            return Some(advice(
+                EconAction::Roll,
+                format!("{} HP left: roll down to 10 to stop losing", hp),
+                INTEREST_BREAKPOINTS[0],
+                trace.fired("econ.low-hp"),
+            ));
+        }
+        if trace.check("streak", "<=", -LOSS_STREAK, state.streak <= -LOSS_STREAK)
+            && trace.check("hp", "<=", STREAK_HP, hp <= STREAK_HP)
+            && trace.check(
+                "gold",
+                ">",
+                INTEREST_BREAKPOINTS[1],
+                gold > INTEREST_BREAKPOINTS[1],
+            )
+        {
+            return Some(advice(
+                EconAction::Roll,
+                format!(
+                    "{}-round loss streak at {} HP: roll down to 20 to break it",
+                    -state.streak, hp
+                ),
+                INTEREST_BREAKPOINTS[1],
+                trace.fired("econ.loss-streak"),
+            ));
+        }
+    }
+
+    if let Some(target) = target_level(stage, gold, hp) {
+        if trace.check("level", "<", target, state.level < target)
+            && trace.check("gold", ">=", XP_COST, gold >= XP_COST)
+        {
+            return Some(advice(
+                EconAction::Level,
+                format!("Level {} is standard at {}", target, state.stage),
+                breakpoint_at_or_below(gold - XP_COST),
+                trace.fired("econ.level-timing"),
+            ));
+        }
+    }
+
+    let max = INTEREST_BREAKPOINTS[INTEREST_BREAKPOINTS.len() - 1];
+    if trace.check("gold", ">", max, gold > max) {
+        return Some(advice(
+            EconAction::SlowRoll,
+            format!("Interest is capped at {}: roll the gold above it", max),
+            max,
+            trace.fired("econ.interest-cap"),
+        ));
+    }
+
+    let next = INTEREST_BREAKPOINTS
+        .iter()
+        .copied()
+        .find(|&b| b > gold)
+        .unwrap_or(max);
+    let reason = if gold == max {
+        "At max interest: hold 50".to_string()
+    } else {
+        format!("{} gold to the next interest breakpoint", next - gold)
+    };
+    Some(advice(
+        EconAction::Save,
+        reason,
+        next,
+        trace.fired("econ.save"),
+    ))
+}
+
+/// Level the player should be at by now
+fn target_level(stage: (u32, u32), gold: u32, hp: Option<u32>) -> Option<u32> {
+    let standard = LEVEL_TIMINGS
+        .iter()
+        .rev()
+        .find(|(from, _)| stage >= *from)
+        .map(|(_, level)| *level)?;
+
+    let fast_8 =
+        stage >= FAST_8_STAGE && (gold >= FAST_8_GOLD || hp.is_some_and(|hp| hp >= FAST_8_HP));
+    Some(if fast_8 { standard.max(8) } else { standard })
+}
+
+/// Highest interest breakpoint not above `gold`, 0 below the first one
+pub(crate) fn breakpoint_at_or_below(gold: u32) -> u32 {
+    INTEREST_BREAKPOINTS
+        .iter()
+        .copied()
+        .filter(|&b| b <= gold)
+        .max()
+        .unwrap_or(0)
+}
+
+fn advice(action: EconAction, reason: String, target_gold: u32, trace: AdviceTrace) -> EconAdvice {
+    EconAdvice {
+        action,
+        reason,
+        target_gold: Some(target_gold),
+        trace,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(stage: &str, gold: u32, level: u32, hp: u32, streak: i32) -> GameState {
+        GameState {
+            stage: stage.to_string(),
+            gold,
+            level,
+            hp,
+            streak,
+            ..Default::default()
+        }
+    }
+
+    fn action(state: &GameState) -> (EconAction, Option<u32>) {
+        let advice = econ_advice(state).unwrap();
+        (advice.action, advice.target_gold)
+    }
+
+    #[test]
+    fn test_level_timings_and_interest() {
+        // Behind the curve: level, keeping the highest breakpoint possible
+        assert_eq!(
+            action(&state("3-2", 34, 5, 80, 0)),
+            (EconAction::Level, Some(30))
+        );
+        assert_eq!(
+            action(&state("2-1", 6, 3, 100, 0)),
+            (EconAction::Level, Some(0))
+        );
+        // On the curve: save to the next breakpoint
+        assert_eq!(
+            action(&state("3-3", 26, 6, 80, 0)),
+            (EconAction::Save, Some(30))
+        );
+        // 4-2 goes to 8 only with gold or HP to spare
+        assert_eq!(
+            action(&state("4-2", 54, 7, 45, 0)),
+            (EconAction::Level, Some(50))
+        );
+        assert_eq!(
+            action(&state("4-2", 44, 7, 45, 0)),
+            (EconAction::Save, Some(50))
+        );
+        assert_eq!(
+            action(&state("5-1", 44, 7, 45, 0)),
+            (EconAction::Level, Some(40))
+        );
+        // Above max interest and on level: roll the excess
+        assert_eq!(
+            action(&state("4-5", 62, 8, 70, 0)),
+            (EconAction::SlowRoll, Some(50))
+        );
+    }
+
+    #[test]
+    fn test_roll_down_triggers() {
+        assert_eq!(
+            action(&state("4-3", 45, 8, 18, 0)),
+            (EconAction::Roll, Some(0))
+        );
+        assert_eq!(
+            action(&state("4-3", 45, 8, 35, 0)),
+            (EconAction::Roll, Some(10))
+        );
+        let streak = econ_advice(&state("3-5", 32, 6, 48, -4)).unwrap();
+        assert_eq!(streak.action, EconAction::Roll);
+        assert_eq!(streak.target_gold, Some(20));
+        assert!(streak.reason.contains("4-round loss streak"));
+        // The trace shows the HP checks that failed before the streak rule fired
+        assert_eq!(streak.trace.rule, "econ.loss-streak");
+        assert_eq!(
+            streak.trace.summary(),
+            "econ.loss-streak (gold=32, level=6, hp=48, stage=3-5, streak=-4; \
+             streak <= -3, hp <= 50, gold > 20)"
+        );
+        assert!(!streak.trace.checks[0].passed);
+
+        // Unknown HP never triggers a roll-down
+        assert_eq!(
+            action(&state("3-5", 32, 6, 0, -4)),
+            (EconAction::Save, Some(40))
+        );
+        // Nothing to advise before the HUD is read
+        assert!(econ_advice(&GameState::new()).is_none());
+    }
+}
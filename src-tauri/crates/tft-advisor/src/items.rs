@@ -0,0 +1,250 @@
+use crate::comp::unit_matches;
+use crate::{AdviceTrace, ItemAdvice};
+use tft_data::{GameData, MetaComp};
+use tft_state::{parse_stage, GameState};
+
+/// Items a champion can hold
+const MAX_UNIT_ITEMS: usize = 3;
+
+/// From this stage, or at this HP, a non-core item is worth slamming for tempo
+const SLAM_STAGE: u32 = 4;
+const SLAM_HP: u32 = 40;
+
+/// Priority of a core item for a carrier that is on the board
+const PRIORITY_CORE: u32 = 1;
+/// Priority of a core item whose carrier is not on the board yet
+const PRIORITY_CORE_WAITING: u32 = 2;
+/// Priority of any other item, when tempo matters
+const PRIORITY_SLAM: u32 = 3;
+
+/// Which completed items to build from the components on the item bench, most
+/// important first (priority 1). Each component is used at most once.
+pub fn item_advice(state: &GameState, data: &GameData, comp: Option<&MetaComp>) -> Vec<ItemAdvice> {
+    let components: Vec<&str> = state
+        .items
+        .iter()
+        .filter(|i| i.is_component)
+        .map(|i| i.id.as_str())
+        .collect();
+
+    let stage = parse_stage(&state.stage)
+        .map(|(stage, _)| stage)
+        .unwrap_or(0);
+    let slam = stage >= SLAM_STAGE || (state.hp > 0 && state.hp <= SLAM_HP);
+    let slam_on = slam_carrier(state);
+
+    // Every buildable pair, scored
+    let mut candidates: Vec<(usize, usize, ItemAdvice)> = Vec::new();
+    for i in 0..components.len() {
+        for j in i + 1..components.len() {
+            let Some(result) = data.combine(components[i], components[j]) else {
+                continue;
+            };
+            let advice = match core_carrier(state, data, comp, &result.id) {
+                Some((carrier, on_board)) => ItemAdvice {
+                    item1: components[i].to_string(),
+                    item2: components[j].to_string(),
+                    result: result.id.clone(),
+                    priority: if on_board {
+                        PRIORITY_CORE
+                    } else {
+                        PRIORITY_CORE_WAITING
+                    },
+                    reason: if on_board {
+                        format!("Build {} now on {}", result.name, carrier)
+                    } else {
+                        format!(
+                            "{} for {}, who is not on the board yet",
+                            result.name, carrier
+                        )
+                    },
+                    trace: AdviceTrace::new(if on_board {
+                        "items.core-carrier"
+                    } else {
+                        "items.core-waiting"
+                    })
+                    .input("carrier", carrier.as_str()),
+                },
+                None if slam => ItemAdvice {
+                    item1: components[i].to_string(),
+                    item2: components[j].to_string(),
+                    result: result.id.clone(),
+                    priority: PRIORITY_SLAM,
+                    reason: match slam_on {
+                        Some(carrier) => {
+                            format!("Slam {} on {} for tempo", result.name, carrier)
+                        }
+                        None => format!("Slam {} for tempo", result.name),
+                    },
+                    trace: AdviceTrace::new("items.slam").input("carrier", slam_on),
+                },
+                None => continue,
+            };
+            candidates.push((i, j, advice));
+        }
+    }
+    candidates.sort_by_key(|(_, _, advice)| advice.priority);
+
+    let mut used = vec![false; components.len()];
+    let mut advice = Vec::new();
+    for (n, (i, j, item)) in candidates.iter().enumerate() {
+        if used[*i] || used[*j] {
+            continue;
+        }
+        used[*i] = true;
+        used[*j] = true;
+
+        let mut item = item.clone();
+        item.trace = item
+            .trace
+            .input("stage", stage)
+            .input("hp", state.hp)
+            .input("components", components.len());
+        item.trace
+            .check("stage", ">=", SLAM_STAGE, stage >= SLAM_STAGE);
+        item.trace
+            .check("hp", "<=", SLAM_HP, state.hp > 0 && state.hp <= SLAM_HP);
+        // Other items the same components could have gone into, scored by priority
+        for (k, l, other) in &candidates[n + 1..] {
+            if [k, l].iter().any(|c| *c == i || *c == j) {
+                item.trace.alternative(&other.result, other.priority as f64);
+            }
+        }
+        advice.push(item);
+    }
+    advice
+}
+
+/// Who a tempo item goes on: the board unit already holding the most items,
+/// then the most expensive and highest starred, with a free item slot
+fn slam_carrier(state: &GameState) -> Option<&str> {
+    state
+        .board
+        .iter()
+        .filter(|slot| slot.items.len() < MAX_UNIT_ITEMS)
+        .filter_map(|slot| Some((slot, slot.champion.as_ref()?)))
+        .max_by_key(|(slot, c)| (slot.items.len(), c.cost, slot.star_level))
+        .map(|(_, c)| c.name.trim())
+}
+
+/// The comp carrier a completed item is core for, by display name, and
+/// whether that carrier is on the board with a free item slot
+fn core_carrier(
+    state: &GameState,
+    data: &GameData,
+    comp: Option<&MetaComp>,
+    item_id: &str,
+) -> Option<(String, bool)> {
+    let comp = comp?;
+    let (unit, _) = comp
+        .core_items
+        .iter()
+        .filter(|(_, items)| items.iter().any(|i| i == item_id))
+        // Prefer carriers listed first in the comp
+        .min_by_key(|(unit, _)| {
+            let position = comp.core_units.iter().position(|u| u == *unit);
+            (position.unwrap_or(usize::MAX), *unit)
+        })?;
+
+    let on_board = state.board.iter().any(|slot| {
+        slot.items.len() < MAX_UNIT_ITEMS
+            && slot
+                .champion
+                .as_ref()
+                .is_some_and(|c| unit_matches(unit, c))
+    });
+    let name = data
+        .champions
+        .get(unit)
+        .map(|c| c.name.trim().to_string())
+        .unwrap_or_else(|| unit.clone());
+    Some((name, on_board))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tft_state::{BoardSlot, Champion, Item};
+
+    fn component(id: &str) -> Item {
+        Item {
+            id: format!("TFT_Item_{}", id),
+            name: id.to_string(),
+            is_component: true,
+        }
+    }
+
+    fn comp() -> MetaComp {
+        MetaComp {
+            name: "Jinx Reroll".to_string(),
+            tier: "S".to_string(),
+            core_units: vec!["Jinx".to_string(), "Vi".to_string()],
+            flex_units: Vec::new(),
+            core_items: HashMap::from([
+                (
+                    "Jinx".to_string(),
+                    vec!["TFT_Item_GuinsoosRageblade".to_string()],
+                ),
+                ("Vi".to_string(), vec!["TFT_Item_BrambleVest".to_string()]),
+            ]),
+            early_game: Vec::new(),
+            power_spike: "3-2".to_string(),
+        }
+    }
+
+    fn state(stage: &str, items: &[&str]) -> GameState {
+        GameState {
+            stage: stage.to_string(),
+            items: items.iter().map(|id| component(id)).collect(),
+            board: vec![BoardSlot {
+                row: 0,
+                col: 0,
+                champion: Some(Champion {
+                    id: "Jinx".to_string(),
+                    name: "Jinx".to_string(),
+                    cost: 2,
+                    traits: Vec::new(),
+                }),
+                star_level: 2,
+                items: Vec::new(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_core_items_first() {
+        let data = GameData::load(std::path::Path::new("/nonexistent")).unwrap();
+        let state = state(
+            "3-2",
+            &["ChainVest", "RecurveBow", "NeedlesslyLargeRod", "ChainVest"],
+        );
+
+        let advice = item_advice(&state, &data, Some(&comp()));
+        assert_eq!(advice.len(), 2);
+        assert_eq!(advice[0].result, "TFT_Item_GuinsoosRageblade");
+        assert_eq!(advice[0].priority, PRIORITY_CORE);
+        assert_eq!(advice[0].reason, "Build Guinsoo's Rageblade now on Jinx");
+        assert_eq!(advice[0].trace.rule, "items.core-carrier");
+        // Vi is not on the board: still worth building, but second
+        assert_eq!(advice[1].result, "TFT_Item_BrambleVest");
+        assert_eq!(advice[1].priority, PRIORITY_CORE_WAITING);
+    }
+
+    #[test]
+    fn test_slam_only_when_tempo_matters() {
+        let data = GameData::load(std::path::Path::new("/nonexistent")).unwrap();
+        assert!(item_advice(&state("3-2", &["BFSword", "BFSword"]), &data, None).is_empty());
+
+        let advice = item_advice(
+            &state("4-1", &["BFSword", "BFSword", "Spatula"]),
+            &data,
+            None,
+        );
+        assert_eq!(advice.len(), 1);
+        assert_eq!(advice[0].result, "TFT_Item_Deathblade");
+        assert_eq!(advice[0].priority, PRIORITY_SLAM);
+        assert_eq!(advice[0].reason, "Slam Deathblade on Jinx for tempo");
+    }
+}
@@ -1,37 +1,91 @@
+mod augments;
+mod comp;
+mod econ;
+mod items;
+#[cfg(feature = "llm")]
+mod llm;
+mod manager;
+mod odds;
+mod positioning;
+mod registry;
+mod review;
+mod rules;
+mod shop;
+mod trace;
+
+pub use augments::augment_advice;
+pub use comp::{best_comp, comp_advice, comp_score};
+pub use econ::econ_advice;
+pub use items::item_advice;
+#[cfg(feature = "llm")]
+pub use llm::{build_prompt, LlmClient, LlmConfig, LlmInsight};
+pub use manager::{AdviceKind, AdviceManager, AdviceRecord};
+pub use odds::{roll_odds, RollOdds, RollQuery, RollSetup};
+pub use positioning::positioning_advice;
+pub use registry::{
+    AdviceContext, Advisor, AdvisorRegistry, AugmentAdvisor, CompAdvisor, EconAdvisor, ItemAdvisor,
+    PositioningAdvisor, ShopAdvisor, AUGMENT_PRIORITY, COMP_PRIORITY, ECON_PRIORITY, ITEM_PRIORITY,
+    POSITIONING_PRIORITY, SHOP_PRIORITY,
+};
+pub use review::{review_match, review_timeline, ReviewReport, RoundActions, RoundReview};
+pub use rules::{RuleAdvisor, RuleError, RuleSet, RULES_FILE};
+pub use shop::shop_advice;
+pub use trace::{AdviceTrace, TraceAlternative, TraceCheck, TraceInput};
+
 use serde::{Deserialize, Serialize};
 
 /// Advice generated by the rule engine or LLM
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Advice {
     pub shop: Vec<ShopAdvice>,
     pub econ: Option<EconAdvice>,
     pub items: Vec<ItemAdvice>,
+    /// Best-matching comp
     pub comp: Option<CompAdvice>,
+    /// Closest comps, best first
+    pub comps: Vec<CompAdvice>,
+    #[serde(default)]
+    pub positioning: Vec<PositioningAdvice>,
+    /// Offered augments, best first; empty outside augment selection
+    #[serde(default)]
+    pub augments: Vec<AugmentAdvice>,
     pub llm_insight: Option<String>,
+    /// Video position (ms) of the state the advice was generated for
+    #[serde(default)]
+    pub timestamp: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ShopAdvice {
     pub slot_index: usize,
     pub action: ShopAction,
     pub reason: String,
+    #[serde(default)]
+    pub trace: AdviceTrace,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum ShopAction {
     Buy,
     Skip,
     Consider,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct EconAdvice {
     pub action: EconAction,
     pub reason: String,
     pub target_gold: Option<u32>,
+    #[serde(default)]
+    pub trace: AdviceTrace,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum EconAction {
     Level,
     Roll,
@@ -39,25 +93,159 @@ pub enum EconAction {
     SlowRoll,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ItemAdvice {
     pub item1: String,
     pub item2: String,
     pub result: String,
     pub priority: u32,
     pub reason: String,
+    #[serde(default)]
+    pub trace: AdviceTrace,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CompAdvice {
     pub comp_name: String,
     pub match_score: f64,
     pub missing_units: Vec<String>,
     pub core_items: Vec<String>,
+    #[serde(default)]
+    pub trace: AdviceTrace,
+}
+
+/// A board hex. Row 0 is the front row, row 3 the back row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Hex {
+    pub row: u32,
+    pub col: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositioningAdvice {
+    /// Champion ID
+    pub champion: String,
+    pub hex: Hex,
+    /// `None` when the unit only needs room around it
+    pub move_to: Option<Hex>,
+    pub reason: String,
+    #[serde(default)]
+    pub trace: AdviceTrace,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AugmentAdvice {
+    /// Card position on the selection screen
+    pub slot: usize,
+    pub augment_id: String,
+    pub name: String,
+    /// Higher is better; a tier list S is 1.0 before adjustments
+    pub score: f64,
+    /// The tier and every adjustment to it, one line each
+    pub reasons: Vec<String>,
+    #[serde(default)]
+    pub trace: AdviceTrace,
 }
 
-/// Placeholder for the advisor pipeline.
-/// Will be implemented in Phase 4 with rule engine.
-pub fn generate_advice(_state: &tft_state::GameState) -> Advice {
-    Advice::default()
+impl Advice {
+    /// Whether two advice sets tell the player the same thing, ignoring the
+    /// traces (whose inputs jitter from frame to frame) and the timestamp
+    pub fn same_content(&self, other: &Advice) -> bool {
+        self.without_traces() == other.without_traces()
+    }
+
+    fn without_traces(&self) -> Advice {
+        let mut advice = Advice {
+            timestamp: 0,
+            ..self.clone()
+        };
+        advice
+            .shop
+            .iter_mut()
+            .for_each(|a| a.trace = AdviceTrace::default());
+        advice
+            .econ
+            .iter_mut()
+            .for_each(|a| a.trace = AdviceTrace::default());
+        advice
+            .items
+            .iter_mut()
+            .for_each(|a| a.trace = AdviceTrace::default());
+        advice
+            .comp
+            .iter_mut()
+            .chain(&mut advice.comps)
+            .for_each(|a| a.trace = AdviceTrace::default());
+        advice
+            .positioning
+            .iter_mut()
+            .for_each(|a| a.trace = AdviceTrace::default());
+        advice
+            .augments
+            .iter_mut()
+            .for_each(|a| a.trace = AdviceTrace::default());
+        advice
+    }
+}
+
+/// Run the built-in rule modules on a game state
+pub fn generate_advice(state: &tft_state::GameState, data: &tft_data::GameData) -> Advice {
+    AdvisorRegistry::default().advise(state, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_like_frontend_types() {
+        let advice = Advice {
+            shop: vec![ShopAdvice {
+                slot_index: 2,
+                action: ShopAction::Buy,
+                reason: "Core unit".to_string(),
+                trace: AdviceTrace::default(),
+            }],
+            econ: Some(EconAdvice {
+                action: EconAction::SlowRoll,
+                reason: "Above 50".to_string(),
+                target_gold: Some(50),
+                trace: AdviceTrace::new("econ.interest-cap").input("gold", 62),
+            }),
+            timestamp: 1500,
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&advice).unwrap();
+        assert_eq!(json["shop"][0]["slotIndex"], 2);
+        assert_eq!(json["shop"][0]["action"], "buy");
+        assert_eq!(json["econ"]["action"], "slow-roll");
+        assert_eq!(json["econ"]["targetGold"], 50);
+        assert!(json["llmInsight"].is_null());
+        assert_eq!(json["timestamp"], 1500);
+        assert_eq!(json["econ"]["trace"]["rule"], "econ.interest-cap");
+        assert_eq!(json["econ"]["trace"]["inputs"][0]["value"], 62);
+
+        let back: Advice = serde_json::from_value(json).unwrap();
+        assert_eq!(back, advice);
+    }
+
+    #[test]
+    fn test_same_content_ignores_traces() {
+        let advice = |gold: u32, action| Advice {
+            econ: Some(EconAdvice {
+                action,
+                reason: "Above 50".to_string(),
+                target_gold: Some(50),
+                trace: AdviceTrace::new("econ.interest-cap").input("gold", gold),
+            }),
+            timestamp: gold as u64,
+            ..Default::default()
+        };
+        assert!(advice(62, EconAction::SlowRoll).same_content(&advice(63, EconAction::SlowRoll)));
+        assert!(!advice(62, EconAction::SlowRoll).same_content(&advice(62, EconAction::Save)));
+    }
 }
@@ -0,0 +1,334 @@
+use crate::Advice;
+use anyhow::{Context, Result};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tft_state::GameState;
+
+/// Default request timeout; a local model that takes longer is not worth waiting for
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Default length of an insight, in tokens
+const DEFAULT_MAX_TOKENS: u32 = 120;
+
+const SYSTEM_PROMPT: &str = "You are a Teamfight Tactics coach. Given the player's game \
+state and the rule engine's advice as JSON, reply with one or two short sentences of \
+advice for this round. Do not repeat the rule engine's advice word for word.";
+
+/// Where and how to reach an OpenAI-compatible chat completions endpoint
+#[derive(Debug, Clone)]
+pub struct LlmConfig {
+    /// Base URL, e.g. "http://localhost:11434/v1"; "/chat/completions" is appended
+    pub endpoint: String,
+    pub model: String,
+    pub api_key: Option<String>,
+    pub timeout: Duration,
+    pub max_tokens: u32,
+}
+
+impl LlmConfig {
+    pub fn new(endpoint: &str, model: &str) -> Self {
+        Self {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            api_key: None,
+            timeout: DEFAULT_TIMEOUT,
+            max_tokens: DEFAULT_MAX_TOKENS,
+        }
+    }
+
+    /// Configuration from `SPAT_LLM_ENDPOINT`, `SPAT_LLM_MODEL` and the optional
+    /// `SPAT_LLM_API_KEY`. `None` when no endpoint is set.
+    pub fn from_env() -> Option<Self> {
+        let endpoint = std::env::var("SPAT_LLM_ENDPOINT").ok()?;
+        let model = std::env::var("SPAT_LLM_MODEL").unwrap_or_else(|_| "default".to_string());
+        let mut config = Self::new(&endpoint, &model);
+        config.api_key = std::env::var("SPAT_LLM_API_KEY").ok();
+        Some(config)
+    }
+}
+
+/// Blocking client for the chat completions endpoint
+#[derive(Clone)]
+pub struct LlmClient {
+    config: LlmConfig,
+    agent: ureq::Agent,
+}
+
+impl LlmClient {
+    pub fn new(config: LlmConfig) -> Self {
+        let agent = ureq::AgentBuilder::new().timeout(config.timeout).build();
+        Self { config, agent }
+    }
+
+    /// Send one chat completion request and return the reply text
+    pub fn complete(&self, prompt: &str) -> Result<String> {
+        let url = format!("{}/chat/completions", self.config.endpoint);
+        let body = json!({
+            "model": self.config.model,
+            "max_tokens": self.config.max_tokens,
+            "temperature": 0.3,
+            "messages": [
+                {"role": "system", "content": SYSTEM_PROMPT},
+                {"role": "user", "content": prompt},
+            ],
+        });
+
+        let mut request = self.agent.post(&url);
+        if let Some(key) = &self.config.api_key {
+            request = request.set("Authorization", &format!("Bearer {}", key));
+        }
+        let response: serde_json::Value = request
+            .send_json(body)
+            .with_context(|| format!("LLM request to {} failed", url))?
+            .into_json()
+            .context("LLM response is not JSON")?;
+
+        let content = response["choices"][0]["message"]["content"]
+            .as_str()
+            .context("LLM response has no message content")?;
+        Ok(content.trim().to_string())
+    }
+}
+
+/// The prompt for a game state and the rule engine's advice
+pub fn build_prompt(state: &GameState, advice: &Advice) -> String {
+    let units = |slots: &[tft_state::BoardSlot]| -> Vec<String> {
+        slots
+            .iter()
+            .filter_map(|s| {
+                let c = s.champion.as_ref()?;
+                Some(format!("{} {}*", c.name, s.star_level.max(1)))
+            })
+            .collect()
+    };
+    let prompt = json!({
+        "state": {
+            "stage": state.stage,
+            "gold": state.gold,
+            "level": state.level,
+            "hp": state.hp,
+            "streak": state.streak,
+            "board": units(&state.board),
+            "bench": units(&state.bench),
+            "items": state.items.iter().map(|i| &i.name).collect::<Vec<_>>(),
+            "traits": state
+                .traits
+                .iter()
+                .map(|t| format!("{} {}", t.name, t.count))
+                .collect::<Vec<_>>(),
+            "augments": state.augments,
+        },
+        "advice": advice,
+    });
+    prompt.to_string()
+}
+
+#[derive(Default)]
+struct Cached {
+    /// Stage the last request was made for
+    stage: Option<String>,
+    insight: Option<String>,
+    /// Whether a request is still waiting for its reply
+    in_flight: bool,
+}
+
+/// Asks the LLM for an insight once per round, in the background.
+///
+/// The request for a round is sent when its stage is first seen; until the
+/// reply arrives, and for the rest of the round, the cached insight is
+/// returned. A failed or slow request just leaves the round without one.
+/// Only one request runs at a time: a new round seen while one is pending
+/// sends its own once the reply (or timeout) is in.
+pub struct LlmInsight {
+    client: LlmClient,
+    cached: Arc<Mutex<Cached>>,
+}
+
+impl LlmInsight {
+    pub fn new(config: LlmConfig) -> Self {
+        tracing::info!(
+            "LLM insight enabled: {} ({})",
+            config.endpoint,
+            config.model
+        );
+        Self {
+            client: LlmClient::new(config),
+            cached: Arc::new(Mutex::new(Cached::default())),
+        }
+    }
+
+    /// Insight for the current round, if one has arrived. On a new stage this
+    /// starts a request and returns `None`.
+    pub fn insight(&self, state: &GameState, advice: &Advice) -> Option<String> {
+        if state.stage.is_empty() {
+            return None;
+        }
+        let mut cached = self.cached.lock().ok()?;
+        if cached.stage.as_deref() == Some(state.stage.as_str()) {
+            return cached.insight.clone();
+        }
+        if cached.in_flight {
+            return None;
+        }
+
+        cached.in_flight = true;
+        cached.stage = Some(state.stage.clone());
+        cached.insight = None;
+        let stage = state.stage.clone();
+        let prompt = build_prompt(state, advice);
+        let client = self.client.clone();
+        let shared = self.cached.clone();
+        std::thread::spawn(move || {
+            let result = client.complete(&prompt);
+            let Ok(mut cached) = shared.lock() else {
+                return;
+            };
+            cached.in_flight = false;
+            // `stage` cannot change while a request is in flight, so the reply
+            // always belongs to it; `insight` only returns it for that stage
+            match result {
+                Ok(insight) => {
+                    tracing::debug!("LLM insight for {}: {}", stage, insight);
+                    cached.insight = Some(insight);
+                }
+                Err(e) => tracing::warn!("No LLM insight for {}: {:#}", stage, e),
+            }
+        });
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Instant;
+
+    /// Minimal HTTP server answering every request with `status` and `body`.
+    /// Returns its base URL and the number of requests served.
+    fn mock_server(status: u16, body: &'static str) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                    if let Some(v) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = v.trim().parse().unwrap();
+                    }
+                }
+                let mut request = vec![0; length];
+                reader.read_exact(&mut request).unwrap();
+                let request: serde_json::Value = serde_json::from_slice(&request).unwrap();
+                assert_eq!(request["model"], "test-model");
+                counter.fetch_add(1, Ordering::SeqCst);
+
+                let response = format!(
+                    "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    const REPLY: &str =
+        r#"{"choices": [{"message": {"role": "assistant", "content": " Slam items now. "}}]}"#;
+
+    fn state(stage: &str) -> GameState {
+        GameState {
+            stage: stage.to_string(),
+            gold: 30,
+            ..Default::default()
+        }
+    }
+
+    /// Poll `insight` until it returns something or a second has passed
+    fn wait_for(insight: &LlmInsight, state: &GameState) -> Option<String> {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(1) {
+            if let Some(text) = insight.insight(state, &Advice::default()) {
+                return Some(text);
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        None
+    }
+
+    #[test]
+    fn test_one_request_per_round() {
+        let (url, requests) = mock_server(200, REPLY);
+        let insight = LlmInsight::new(LlmConfig::new(&url, "test-model"));
+
+        assert!(insight.insight(&state("3-2"), &Advice::default()).is_none());
+        assert_eq!(
+            wait_for(&insight, &state("3-2")).as_deref(),
+            Some("Slam items now.")
+        );
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        // A new round asks again
+        insight.insight(&state("3-3"), &Advice::default());
+        assert!(wait_for(&insight, &state("3-3")).is_some());
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_failures_degrade_gracefully() {
+        let (url, requests) = mock_server(500, "{}");
+        let insight = LlmInsight::new(LlmConfig::new(&url, "test-model"));
+        assert!(wait_for(&insight, &state("4-1")).is_none());
+        // The failed round is not retried
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        // A server that never answers times out
+        let silent = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut config = LlmConfig::new(
+            &format!("http://{}/v1", silent.local_addr().unwrap()),
+            "test-model",
+        );
+        config.timeout = Duration::from_millis(200);
+        let start = Instant::now();
+        assert!(LlmClient::new(config).complete("hi").is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_one_request_in_flight() {
+        // Connections queue up on a listener that never accepts them
+        let silent = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut config = LlmConfig::new(
+            &format!("http://{}/v1", silent.local_addr().unwrap()),
+            "test-model",
+        );
+        config.timeout = Duration::from_millis(300);
+        let insight = LlmInsight::new(config);
+        for stage in ["2-1", "2-2", "2-3"] {
+            assert!(insight.insight(&state(stage), &Advice::default()).is_none());
+        }
+        std::thread::sleep(Duration::from_millis(100));
+        silent.set_nonblocking(true).unwrap();
+        assert_eq!(std::iter::from_fn(|| silent.accept().ok()).count(), 1);
+
+        // Once the first request timed out, the current round gets its own
+        std::thread::sleep(Duration::from_millis(400));
+        assert!(insight.insight(&state("2-3"), &Advice::default()).is_none());
+        std::thread::sleep(Duration::from_millis(100));
+        assert_eq!(std::iter::from_fn(|| silent.accept().ok()).count(), 1);
+    }
+}
@@ -0,0 +1,430 @@
+use crate::{Advice, CompAdvice, EconAction, EconAdvice, ShopAction};
+use serde::{Deserialize, Serialize};
+use tft_state::{parse_stage, GameEvent, GameEventKind, GameState};
+
+/// Consecutive updates a less urgent econ action must persist before it
+/// replaces the one on screen
+const STABLE_UPDATES: u32 = 3;
+
+/// Match score a comp needs over the shown one to replace it mid-round
+const COMP_SWITCH_MARGIN: f64 = 0.1;
+
+/// What kind of recommendation a record is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AdviceKind {
+    Econ,
+    Shop,
+    Item,
+}
+
+/// One recommendation as the player saw it, and whether they acted on it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdviceRecord {
+    pub kind: AdviceKind,
+    /// The econ action, champion to buy or item to build
+    pub target: String,
+    pub reason: String,
+    pub stage: String,
+    pub first_shown_ms: u64,
+    pub last_shown_ms: u64,
+    /// `None` while the advice is still on screen
+    pub followed: Option<bool>,
+}
+
+/// Identity of a recommendation on screen
+#[derive(Debug, Clone, PartialEq)]
+enum Shown {
+    Econ(EconAction),
+    /// Champion ID
+    Shop(String),
+    /// Completed item ID
+    Item(String),
+}
+
+/// Keeps the advice on screen stable and records what became of it.
+///
+/// Fresh advice is computed on every state, but small read jitter must not
+/// make the top recommendation flicker: a different econ action only replaces
+/// the shown one when it is a roll-down, the round changed, or it held for a
+/// few updates; a different best comp only when it scores clearly higher.
+/// Econ, buy and item recommendations are recorded with the time they were
+/// first shown and, from the game events that follow, whether the player
+/// did what was suggested.
+#[derive(Debug, Default)]
+pub struct AdviceManager {
+    current: Option<Advice>,
+    /// Latest stage read, ignoring misreads that go backwards
+    stage: String,
+    /// A different econ action waiting to be shown, and for how many updates
+    pending: Option<(EconAction, u32)>,
+    records: Vec<AdviceRecord>,
+    /// Records of the advice on screen, by index
+    open: Vec<(usize, Shown)>,
+}
+
+impl AdviceManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advice currently shown
+    pub fn current(&self) -> Option<&Advice> {
+        self.current.as_ref()
+    }
+
+    /// Whether a different econ action is waiting for more updates before it
+    /// replaces the shown one
+    pub fn has_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Every recommendation shown so far, in the order it first appeared
+    pub fn records(&self) -> &[AdviceRecord] {
+        &self.records
+    }
+
+    /// Check the player's actions against the advice on screen. Call with the
+    /// events leading up to a state before `update` with that state's advice.
+    pub fn observe_events(&mut self, events: &[GameEvent]) {
+        for event in events {
+            for (i, shown) in &self.open {
+                let record = &mut self.records[*i];
+                if record.followed.is_none() {
+                    record.followed = follows(shown, &event.kind);
+                }
+            }
+            if matches!(event.kind, GameEventKind::RoundEnded { .. }) {
+                self.close_all();
+            }
+        }
+    }
+
+    /// Take freshly generated advice and return what should be shown
+    pub fn update(&mut self, state: &GameState, mut advice: Advice, timestamp_ms: u64) -> &Advice {
+        // Empty or flickering stage reads must not count as new rounds
+        let round_changed = match (parse_stage(&self.stage), parse_stage(&state.stage)) {
+            (Some(old), Some(new)) => new > old,
+            (None, Some(_)) => true,
+            _ => false,
+        };
+        if round_changed {
+            self.stage = state.stage.clone();
+        }
+
+        if let Some(current) = self.current.take() {
+            advice.econ = self.stable_econ(current.econ, advice.econ, round_changed);
+            advice.comp = stable_comp(current.comp, advice.comp, &advice.comps, round_changed);
+        }
+        self.track(state, &advice, timestamp_ms);
+        self.current.insert(advice)
+    }
+
+    fn stable_econ(
+        &mut self,
+        current: Option<EconAdvice>,
+        new: Option<EconAdvice>,
+        round_changed: bool,
+    ) -> Option<EconAdvice> {
+        let Some(current) = current else {
+            self.pending = None;
+            return new;
+        };
+        let Some(new) = new else {
+            // A missed HUD read keeps the advice for the rest of the round
+            return (!round_changed).then_some(current);
+        };
+        // Roll-downs protect HP and show at once
+        if new.action == current.action || round_changed || new.action == EconAction::Roll {
+            self.pending = None;
+            return Some(new);
+        }
+
+        let updates = match self.pending {
+            Some((action, n)) if action == new.action => n + 1,
+            _ => 1,
+        };
+        if updates >= STABLE_UPDATES {
+            self.pending = None;
+            Some(new)
+        } else {
+            self.pending = Some((new.action, updates));
+            Some(current)
+        }
+    }
+
+    /// Open records for new recommendations, refresh the ones still shown and
+    /// close the ones that went away
+    fn track(&mut self, state: &GameState, advice: &Advice, timestamp_ms: u64) {
+        let mut shown: Vec<(Shown, AdviceKind, String, &str)> = Vec::new();
+        if let Some(econ) = &advice.econ {
+            let target = serde_json::to_value(econ.action)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default();
+            shown.push((
+                Shown::Econ(econ.action),
+                AdviceKind::Econ,
+                target,
+                &econ.reason,
+            ));
+        }
+        for shop in advice.shop.iter().filter(|s| s.action == ShopAction::Buy) {
+            let slot = state.shop.iter().find(|s| s.index == shop.slot_index);
+            if let Some(champion) = slot.and_then(|s| s.champion.as_ref()) {
+                shown.push((
+                    Shown::Shop(champion.id.clone()),
+                    AdviceKind::Shop,
+                    champion.id.clone(),
+                    &shop.reason,
+                ));
+            }
+        }
+        for item in &advice.items {
+            shown.push((
+                Shown::Item(item.result.clone()),
+                AdviceKind::Item,
+                item.result.clone(),
+                &item.reason,
+            ));
+        }
+
+        let gone: Vec<usize> = self
+            .open
+            .iter()
+            .filter(|(_, open)| !shown.iter().any(|(s, ..)| s == open))
+            .map(|(i, _)| *i)
+            .collect();
+        for i in gone {
+            self.close(i);
+        }
+
+        for (key, kind, target, reason) in shown {
+            if let Some((i, _)) = self.open.iter().find(|(_, open)| *open == key) {
+                self.records[*i].last_shown_ms = timestamp_ms;
+                continue;
+            }
+            self.open.push((self.records.len(), key));
+            self.records.push(AdviceRecord {
+                kind,
+                target,
+                reason: reason.to_string(),
+                stage: state.stage.clone(),
+                first_shown_ms: timestamp_ms,
+                last_shown_ms: timestamp_ms,
+                followed: None,
+            });
+        }
+    }
+
+    /// Take a record off screen. Without a telling event, saving counts as
+    /// followed and everything else as ignored.
+    fn close(&mut self, index: usize) {
+        let Some(position) = self.open.iter().position(|(i, _)| *i == index) else {
+            return;
+        };
+        let (_, shown) = self.open.remove(position);
+        let record = &mut self.records[index];
+        if record.followed.is_none() {
+            record.followed = Some(shown == Shown::Econ(EconAction::Save));
+        }
+    }
+
+    fn close_all(&mut self) {
+        let open: Vec<usize> = self.open.iter().map(|(i, _)| *i).collect();
+        for i in open {
+            self.close(i);
+        }
+    }
+}
+
+/// Keep the shown comp unless the new best one is clearly better
+fn stable_comp(
+    current: Option<CompAdvice>,
+    new: Option<CompAdvice>,
+    comps: &[CompAdvice],
+    round_changed: bool,
+) -> Option<CompAdvice> {
+    let (Some(current), Some(best)) = (current, &new) else {
+        return new;
+    };
+    if round_changed || best.comp_name == current.comp_name {
+        return new;
+    }
+    // The shown comp, scored against the new state
+    match comps.iter().find(|c| c.comp_name == current.comp_name) {
+        Some(rescored) if best.match_score - rescored.match_score < COMP_SWITCH_MARGIN => {
+            Some(rescored.clone())
+        }
+        _ => new,
+    }
+}
+
+/// Whether an event shows the player following (or going against) advice
+fn follows(shown: &Shown, event: &GameEventKind) -> Option<bool> {
+    match (shown, event) {
+        (Shown::Econ(EconAction::Roll | EconAction::SlowRoll), GameEventKind::ShopRerolled) => {
+            Some(true)
+        }
+        (
+            Shown::Econ(EconAction::Level),
+            GameEventKind::XpPurchased | GameEventKind::LevelUp { .. },
+        ) => Some(true),
+        (
+            Shown::Econ(EconAction::Save),
+            GameEventKind::ShopRerolled | GameEventKind::XpPurchased,
+        ) => Some(false),
+        (Shown::Shop(id), GameEventKind::ChampionBought { champion_id, .. })
+            if champion_id == id =>
+        {
+            Some(true)
+        }
+        (Shown::Item(id), GameEventKind::ItemCombined { item_id, .. }) if item_id == id => {
+            Some(true)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AdviceTrace, ShopAdvice};
+    use tft_state::{Champion, ShopSlot};
+
+    fn econ(action: EconAction) -> Advice {
+        Advice {
+            econ: Some(EconAdvice {
+                action,
+                reason: format!("{:?}", action),
+                target_gold: None,
+                trace: AdviceTrace::default(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn comp(name: &str, match_score: f64) -> CompAdvice {
+        CompAdvice {
+            comp_name: name.to_string(),
+            match_score,
+            missing_units: Vec::new(),
+            core_items: Vec::new(),
+            trace: AdviceTrace::default(),
+        }
+    }
+
+    fn state(stage: &str) -> GameState {
+        GameState {
+            stage: stage.to_string(),
+            shop: vec![ShopSlot {
+                index: 0,
+                champion: Some(Champion {
+                    id: "Jinx".to_string(),
+                    name: "Jinx".to_string(),
+                    cost: 2,
+                    traits: Vec::new(),
+                }),
+                confidence: 0.9,
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn event(stage: &str, kind: GameEventKind) -> GameEvent {
+        GameEvent {
+            timestamp_ms: 0,
+            stage: stage.to_string(),
+            kind,
+        }
+    }
+
+    fn shown(manager: &AdviceManager) -> EconAction {
+        manager.current().unwrap().econ.as_ref().unwrap().action
+    }
+
+    #[test]
+    fn test_top_recommendation_is_stable() {
+        let mut manager = AdviceManager::new();
+        manager.update(&state("3-2"), econ(EconAction::Save), 0);
+
+        // An unread stage in between is not a new round
+        manager.update(&state(""), econ(EconAction::Level), 1);
+        manager.update(&state("3-2"), econ(EconAction::Level), 1);
+        assert_eq!(shown(&manager), EconAction::Save);
+        manager.update(&state("3-2"), econ(EconAction::Save), 1);
+
+        // A jittery read suggesting a level has to persist before it shows
+        manager.update(&state("3-2"), econ(EconAction::Level), 1);
+        manager.update(&state("3-2"), econ(EconAction::Save), 2);
+        manager.update(&state("3-2"), econ(EconAction::Level), 3);
+        manager.update(&state("3-2"), econ(EconAction::Level), 4);
+        assert_eq!(shown(&manager), EconAction::Save);
+        manager.update(&state("3-2"), econ(EconAction::Level), 5);
+        assert_eq!(shown(&manager), EconAction::Level);
+
+        // A roll-down and a new round show at once
+        manager.update(&state("3-2"), econ(EconAction::Roll), 6);
+        assert_eq!(shown(&manager), EconAction::Roll);
+        manager.update(&state("3-3"), econ(EconAction::Save), 7);
+        assert_eq!(shown(&manager), EconAction::Save);
+
+        // The shown comp stays until another beats it by the margin
+        let comps = |a: f64, b: f64| Advice {
+            comp: Some(if a >= b { comp("A", a) } else { comp("B", b) }),
+            comps: vec![comp("A", a), comp("B", b)],
+            ..econ(EconAction::Save)
+        };
+        manager.update(&state("3-3"), comps(0.5, 0.4), 8);
+        manager.update(&state("3-3"), comps(0.5, 0.55), 9);
+        let current = manager.current().unwrap().comp.as_ref().unwrap();
+        assert_eq!(current.comp_name, "A");
+        manager.update(&state("3-3"), comps(0.5, 0.7), 10);
+        let current = manager.current().unwrap().comp.as_ref().unwrap();
+        assert_eq!(current.comp_name, "B");
+    }
+
+    #[test]
+    fn test_records_whether_advice_was_followed() {
+        let mut manager = AdviceManager::new();
+        let buy = Advice {
+            shop: vec![ShopAdvice {
+                slot_index: 0,
+                action: ShopAction::Buy,
+                reason: "Core unit".to_string(),
+                trace: AdviceTrace::default(),
+            }],
+            ..econ(EconAction::Roll)
+        };
+        manager.update(&state("4-1"), buy.clone(), 1000);
+        manager.update(&state("4-1"), buy, 1500);
+        manager.observe_events(&[event("4-1", GameEventKind::ShopRerolled)]);
+        manager.update(&state("4-1"), econ(EconAction::Roll), 2000);
+
+        // The buy left the screen without a purchase; the next round's save
+        // advice holds until the round ends
+        manager.update(&state("4-2"), econ(EconAction::Save), 3000);
+        manager.observe_events(&[event(
+            "4-2",
+            GameEventKind::RoundEnded {
+                round: "4-2".to_string(),
+                outcome: None,
+            },
+        )]);
+
+        let records = manager.records();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].kind, AdviceKind::Econ);
+        assert_eq!(records[0].target, "roll");
+        assert_eq!(records[0].followed, Some(true));
+        assert_eq!(records[1].target, "Jinx");
+        assert_eq!(
+            (records[1].first_shown_ms, records[1].last_shown_ms),
+            (1000, 1500)
+        );
+        assert_eq!(records[1].followed, Some(false));
+        assert_eq!(records[2].target, "save");
+        assert_eq!(records[2].followed, Some(true));
+    }
+}
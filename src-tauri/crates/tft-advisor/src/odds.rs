@@ -0,0 +1,241 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use tft_data::GameData;
+
+/// Gold cost of one shop reroll
+const REROLL_COST: u32 = 2;
+
+/// Champion slots in one shop
+const SHOP_SLOTS: u32 = 5;
+
+/// Copies that take a unit from nothing to 3-star
+const MAX_COPIES: u32 = 9;
+
+/// Gold beyond this does not change the odds noticeably; it bounds the
+/// roll-down table
+const MAX_GOLD: u32 = 300;
+
+/// What the UI asks: odds of hitting `copies_wanted` copies of a unit of
+/// `cost` by rolling down `gold` at `level`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollQuery {
+    pub level: u32,
+    pub cost: u32,
+    pub copies_wanted: u32,
+    pub gold: u32,
+    /// Target champion, used to look up its remaining copies
+    #[serde(default)]
+    pub champion_id: Option<String>,
+    /// Copies of the target left in the pool, when known
+    #[serde(default)]
+    pub remaining: Option<u32>,
+}
+
+/// Result of a roll-down calculation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollOdds {
+    /// Chance (0-1) of hitting every wanted copy with the gold given
+    pub probability: f64,
+    /// Average gold to hit every copy, rerolls plus purchases; `None` when the
+    /// unit cannot show up at this level
+    pub expected_gold: Option<f64>,
+    /// Rerolls the gold pays for if nothing is bought
+    pub max_rolls: u32,
+}
+
+/// Shop model for one target unit
+#[derive(Debug, Clone)]
+pub struct RollSetup {
+    /// Chance that a shop slot rolls the target's cost tier
+    pub tier_chance: f64,
+    /// Copies of the target left in the pool
+    pub target_copies: u32,
+    /// Copies of all units of the tier left in the pool
+    pub tier_copies: u32,
+    pub cost: u32,
+}
+
+impl RollSetup {
+    /// Build the shop model from static data. `target_copies` defaults to a
+    /// full pool; the tier is assumed otherwise untouched.
+    pub fn from_data(
+        data: &GameData,
+        level: u32,
+        cost: u32,
+        target_copies: Option<u32>,
+    ) -> Result<Self> {
+        let tier_chance = data
+            .shop_chance(level, cost)
+            .with_context(|| format!("No shop odds for a {}-cost unit at level {}", cost, level))?;
+        let pool_size = data
+            .pool_size(cost)
+            .with_context(|| format!("No pool size for {}-cost units", cost))?;
+        let units = data.champions.values().filter(|c| c.cost == cost).count() as u32;
+        if units == 0 {
+            bail!(
+                "No {}-cost champions in the champion data. Run scripts/fetch-templates.py",
+                cost
+            );
+        }
+        let target_copies = target_copies.unwrap_or(pool_size);
+        let full_tier = pool_size * units;
+        Ok(Self {
+            tier_chance,
+            target_copies,
+            tier_copies: full_tier.saturating_sub(pool_size - target_copies.min(pool_size)),
+            cost,
+        })
+    }
+
+    /// Chance that one shop slot shows the target after `hits` copies were bought
+    fn slot_chance(&self, hits: u32) -> f64 {
+        let left = self.target_copies.saturating_sub(hits);
+        let tier_left = self.tier_copies.saturating_sub(hits);
+        if left == 0 || tier_left == 0 {
+            return 0.0;
+        }
+        self.tier_chance * left as f64 / tier_left as f64
+    }
+}
+
+/// Chance of hitting `copies_wanted` copies by rolling down `gold`, and the
+/// expected gold to get there. Every shop counts as a paid reroll and each hit
+/// is bought as soon as the gold allows. Asks come from the UI, so more copies
+/// than a 3-star needs and gold beyond `MAX_GOLD` are capped.
+pub fn roll_odds(setup: &RollSetup, copies_wanted: u32, gold: u32) -> RollOdds {
+    let max_rolls = gold / REROLL_COST;
+    let copies_wanted = copies_wanted.min(MAX_COPIES);
+    let gold = gold.min(MAX_GOLD);
+    if copies_wanted == 0 {
+        return RollOdds {
+            probability: 1.0,
+            expected_gold: Some(0.0),
+            max_rolls,
+        };
+    }
+    if copies_wanted > setup.target_copies {
+        return RollOdds {
+            probability: 0.0,
+            expected_gold: None,
+            max_rolls,
+        };
+    }
+
+    // mass[hits][gold]: chance of being at that point of the roll-down
+    let wanted = copies_wanted as usize;
+    let mut mass = vec![vec![0.0f64; gold as usize + 1]; wanted + 1];
+    mass[0][gold as usize] = 1.0;
+
+    // Each roll spends gold, so walking gold downwards sees every state once
+    for g in (REROLL_COST as usize..=gold as usize).rev() {
+        for hits in 0..wanted {
+            let m = std::mem::take(&mut mass[hits][g]);
+            if m == 0.0 {
+                continue;
+            }
+            // Outcomes of one shop, starting from (hits, gold after the reroll)
+            let mut shop = vec![(hits, g - REROLL_COST as usize, m)];
+            for _ in 0..SHOP_SLOTS {
+                let mut next = Vec::with_capacity(shop.len() * 2);
+                for (h, left, p) in shop {
+                    let hit = if h < wanted && left >= setup.cost as usize {
+                        setup.slot_chance(h as u32)
+                    } else {
+                        0.0
+                    };
+                    if hit > 0.0 {
+                        next.push((h + 1, left - setup.cost as usize, p * hit));
+                    }
+                    next.push((h, left, p * (1.0 - hit)));
+                }
+                shop = next;
+            }
+            for (h, left, p) in shop {
+                mass[h][left] += p;
+            }
+        }
+    }
+
+    RollOdds {
+        probability: mass[wanted].iter().sum::<f64>().min(1.0),
+        expected_gold: expected_gold(setup, copies_wanted),
+        max_rolls,
+    }
+}
+
+/// Expected gold with no budget: the mean number of shops until each next copy
+/// shows up, plus the copies themselves
+fn expected_gold(setup: &RollSetup, copies_wanted: u32) -> Option<f64> {
+    let mut rolls = 0.0;
+    for hits in 0..copies_wanted {
+        let chance = setup.slot_chance(hits);
+        if chance <= 0.0 {
+            return None;
+        }
+        rolls += 1.0 / (SHOP_SLOTS as f64 * chance);
+    }
+    Some(rolls * REROLL_COST as f64 + (copies_wanted * setup.cost) as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(tier_chance: f64, target_copies: u32) -> RollSetup {
+        RollSetup {
+            tier_chance,
+            target_copies,
+            tier_copies: 18 * 13,
+            cost: 3,
+        }
+    }
+
+    #[test]
+    fn test_roll_odds() {
+        // Three-starring a 3-cost at level 7 with 50 gold: possible but unlikely
+        let odds = roll_odds(&setup(0.4, 18), 6, 50);
+        assert_eq!(odds.max_rolls, 25);
+        assert!(odds.probability > 0.0 && odds.probability < 0.5);
+
+        // More gold never hurts, fewer copies in the pool always does
+        let richer = roll_odds(&setup(0.4, 18), 6, 100);
+        let contested = roll_odds(&setup(0.4, 8), 6, 50);
+        assert!(richer.probability > odds.probability);
+        assert!(contested.probability < odds.probability);
+
+        // One copy with plenty of gold is close to certain
+        assert!(roll_odds(&setup(0.4, 18), 1, 100).probability > 0.99);
+
+        // Out of the pool or off the odds table: impossible
+        assert_eq!(roll_odds(&setup(0.4, 2), 3, 100).probability, 0.0);
+        let off_table = roll_odds(&setup(0.0, 18), 1, 100);
+        assert_eq!(off_table.probability, 0.0);
+        assert!(off_table.expected_gold.is_none());
+
+        // Not enough gold to buy the copy after rolling
+        assert_eq!(roll_odds(&setup(0.4, 18), 1, 4).probability, 0.0);
+    }
+
+    #[test]
+    fn test_bounds_ui_input() {
+        // Huge asks are capped instead of sizing the table from them
+        let odds = roll_odds(&setup(0.4, 18), u32::MAX, u32::MAX);
+        assert_eq!(odds.max_rolls, u32::MAX / 2);
+        assert!(odds.probability > 0.9);
+        assert_eq!(roll_odds(&setup(0.4, 5), 6, u32::MAX).probability, 0.0);
+
+        // Without champion data the tier size is unknown
+        let data = GameData::load(std::path::Path::new("/nonexistent")).unwrap();
+        let err = RollSetup::from_data(&data, 7, 3, None).unwrap_err();
+        assert!(err.to_string().contains("No 3-cost champions"));
+    }
+
+    #[test]
+    fn test_expected_gold() {
+        // 5 slots at 40% tier odds and 18/234 of the tier: about 6.5 shops
+        let gold = roll_odds(&setup(0.4, 18), 1, 0).expected_gold.unwrap();
+        assert!((gold - (2.0 * 6.5 + 3.0)).abs() < 0.1);
+    }
+}
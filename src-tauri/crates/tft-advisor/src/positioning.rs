@@ -0,0 +1,262 @@
+use crate::comp::unit_matches;
+use crate::{AdviceTrace, Hex, PositioningAdvice};
+use std::collections::HashSet;
+use tft_data::{GameData, MetaComp};
+use tft_state::{BoardSlot, Champion, GameState};
+
+const BOARD_ROWS: u32 = 4;
+const BOARD_COLS: u32 = 7;
+const FRONT_ROW: u32 = 0;
+const BACK_ROW: u32 = BOARD_ROWS - 1;
+
+/// Completed items that make a unit the carry when no meta comp names one
+const CARRY_ITEMS: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Carry,
+    Tank,
+    Other,
+}
+
+impl Role {
+    fn name(self) -> &'static str {
+        match self {
+            Role::Carry => "carry",
+            Role::Tank => "tank",
+            Role::Other => "other",
+        }
+    }
+}
+
+struct Unit<'a> {
+    champion: &'a Champion,
+    hex: Hex,
+    role: Role,
+    /// Attack range in hexes, 0 when unknown
+    range: u32,
+}
+
+/// Where to move units on the board: carries to the back line, ideally a
+/// corner, tanks to the front and melee carries out of the back row.
+///
+/// Carries are the units the meta comp builds items on, or any unit holding
+/// two completed items; tanks are the other melee units.
+pub fn positioning_advice(
+    state: &GameState,
+    data: &GameData,
+    comp: Option<&MetaComp>,
+) -> Vec<PositioningAdvice> {
+    let mut units: Vec<Unit> = state
+        .board
+        .iter()
+        .filter(|slot| slot.row < BOARD_ROWS && slot.col < BOARD_COLS)
+        .filter_map(|slot| unit(slot, data, comp))
+        .collect();
+    // Carries pick their hexes first
+    units.sort_by_key(|u| u.role != Role::Carry);
+
+    let mut taken: HashSet<Hex> = units.iter().map(|u| u.hex).collect();
+    let mut advice = Vec::new();
+    for unit in &units {
+        if let Some(a) = unit_advice(unit, &mut taken) {
+            advice.push(a);
+        }
+    }
+    advice
+}
+
+fn unit<'a>(slot: &'a BoardSlot, data: &GameData, comp: Option<&MetaComp>) -> Option<Unit<'a>> {
+    let champion = slot.champion.as_ref()?;
+    let range = data.champions.get(&champion.id).map_or(0, |c| c.range);
+    let core_carrier =
+        comp.is_some_and(|comp| comp.core_items.keys().any(|u| unit_matches(u, champion)));
+    let completed = slot.items.iter().filter(|i| !i.is_component).count();
+
+    let role = if core_carrier || completed >= CARRY_ITEMS {
+        Role::Carry
+    } else if range == 1 {
+        Role::Tank
+    } else {
+        Role::Other
+    };
+    Some(Unit {
+        champion,
+        hex: Hex {
+            row: slot.row,
+            col: slot.col,
+        },
+        role,
+        range,
+    })
+}
+
+fn unit_advice(unit: &Unit, taken: &mut HashSet<Hex>) -> Option<PositioningAdvice> {
+    let name = unit.champion.name.trim();
+    let mut trace = AdviceTrace::new("positioning")
+        .input("champion", unit.champion.id.as_str())
+        .input("role", unit.role.name())
+        .input("range", unit.range)
+        .input("row", unit.hex.row)
+        .input("col", unit.hex.col);
+
+    let advice =
+        |rule: &str, trace: &AdviceTrace, move_to: Option<Hex>, reason: String| PositioningAdvice {
+            champion: unit.champion.id.clone(),
+            hex: unit.hex,
+            move_to,
+            reason,
+            trace: trace.fired(rule),
+        };
+
+    match unit.role {
+        Role::Carry
+            if unit.range == 1 && trace.check("row", "==", BACK_ROW, unit.hex.row == BACK_ROW) =>
+        {
+            let to = free_hex(&[FRONT_ROW + 1, FRONT_ROW], unit.hex.col, taken);
+            Some(advice(
+                "positioning.melee-carry-back",
+                &trace,
+                to,
+                format!(
+                    "{} is a melee carry: move it forward to reach enemies",
+                    name
+                ),
+            ))
+        }
+        Role::Carry
+            if unit.range > 1 && trace.check("row", "<", BACK_ROW, unit.hex.row < BACK_ROW) =>
+        {
+            let to = corner(taken).or_else(|| free_hex(&[BACK_ROW], unit.hex.col, taken));
+            Some(advice(
+                "positioning.carry-front",
+                &trace,
+                to,
+                format!("Keep {} in the back line, a corner if possible", name),
+            ))
+        }
+        Role::Tank if trace.check("row", ">", FRONT_ROW + 1, unit.hex.row > FRONT_ROW + 1) => {
+            let to = free_hex(&[FRONT_ROW], unit.hex.col, taken);
+            Some(advice(
+                "positioning.tank-back",
+                &trace,
+                to,
+                format!("Put {} in the front row to tank for the carries", name),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// A free back-row corner, claimed
+fn corner(taken: &mut HashSet<Hex>) -> Option<Hex> {
+    let hex = [0, BOARD_COLS - 1]
+        .into_iter()
+        .map(|col| Hex { row: BACK_ROW, col })
+        .find(|hex| !taken.contains(hex))?;
+    taken.insert(hex);
+    Some(hex)
+}
+
+/// The free hex closest to `col` in the first of `rows` that has one, claimed
+fn free_hex(rows: &[u32], col: u32, taken: &mut HashSet<Hex>) -> Option<Hex> {
+    let hex = rows.iter().find_map(|&row| {
+        (0..BOARD_COLS)
+            .map(|c| Hex { row, col: c })
+            .filter(|hex| !taken.contains(hex))
+            .min_by_key(|hex| hex.col.abs_diff(col))
+    })?;
+    taken.insert(hex);
+    Some(hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tft_data::ChampionData;
+    use tft_state::Item;
+
+    fn data() -> GameData {
+        let mut data = GameData::default();
+        for (id, range) in [("Jinx", 4), ("Yasuo", 1), ("Garen", 1), ("Lux", 4)] {
+            data.champions.insert(
+                id.to_string(),
+                ChampionData {
+                    id: id.to_string(),
+                    name: id.to_string(),
+                    cost: 3,
+                    traits: Vec::new(),
+                    icon: String::new(),
+                    range,
+                },
+            );
+        }
+        data
+    }
+
+    fn slot(id: &str, row: u32, col: u32, items: usize) -> BoardSlot {
+        BoardSlot {
+            row,
+            col,
+            champion: Some(Champion {
+                id: id.to_string(),
+                name: id.to_string(),
+                cost: 3,
+                traits: Vec::new(),
+            }),
+            star_level: 2,
+            items: (0..items)
+                .map(|i| Item {
+                    id: format!("Item{}", i),
+                    name: format!("Item{}", i),
+                    is_component: false,
+                })
+                .collect(),
+        }
+    }
+
+    fn board(slots: Vec<BoardSlot>) -> GameState {
+        GameState {
+            stage: "4-1".to_string(),
+            board: slots,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_flags_misplaced_units() {
+        let comp = MetaComp {
+            name: "Jinx".to_string(),
+            tier: "S".to_string(),
+            core_units: vec!["Jinx".to_string()],
+            flex_units: Vec::new(),
+            core_items: HashMap::from([("Jinx".to_string(), Vec::new())]),
+            early_game: Vec::new(),
+            power_spike: "4-1".to_string(),
+        };
+        // Jinx (comp carry) in the front row, Yasuo with three items in the
+        // back row, Garen hiding behind them
+        let state = board(vec![
+            slot("Jinx", 0, 3, 0),
+            slot("Yasuo", 3, 3, 3),
+            slot("Garen", 2, 1, 0),
+            slot("Lux", 3, 0, 0),
+        ]);
+        let advice = positioning_advice(&state, &data(), Some(&comp));
+        assert_eq!(advice.len(), 3);
+
+        let jinx = advice.iter().find(|a| a.champion == "Jinx").unwrap();
+        assert_eq!(jinx.trace.rule, "positioning.carry-front");
+        // Lux holds the left corner
+        assert_eq!(jinx.move_to, Some(Hex { row: 3, col: 6 }));
+
+        let yasuo = advice.iter().find(|a| a.champion == "Yasuo").unwrap();
+        assert_eq!(yasuo.trace.rule, "positioning.melee-carry-back");
+        assert_eq!(yasuo.move_to, Some(Hex { row: 1, col: 3 }));
+
+        let garen = advice.iter().find(|a| a.champion == "Garen").unwrap();
+        assert_eq!(garen.trace.rule, "positioning.tank-back");
+        assert_eq!(garen.move_to, Some(Hex { row: 0, col: 1 }));
+    }
+}
@@ -0,0 +1,395 @@
+use crate::{
+    augment_advice, best_comp, comp_advice, econ_advice, item_advice, positioning_advice,
+    shop_advice, Advice,
+};
+use std::collections::HashMap;
+use tft_data::{GameData, MetaComp};
+use tft_state::GameState;
+
+/// Number of comps suggested at once
+const TOP_COMPS: usize = 3;
+
+/// Priorities of the built-in modules. House rules registered above these
+/// override them, below them only fill gaps.
+pub const ECON_PRIORITY: i32 = 100;
+pub const SHOP_PRIORITY: i32 = 100;
+pub const ITEM_PRIORITY: i32 = 100;
+pub const COMP_PRIORITY: i32 = 100;
+pub const POSITIONING_PRIORITY: i32 = 100;
+pub const AUGMENT_PRIORITY: i32 = 100;
+
+/// What an advisor module gets to look at
+pub struct AdviceContext<'a> {
+    pub state: &'a GameState,
+    pub data: &'a GameData,
+    /// Best-matching meta comp, shared so every module targets the same one
+    pub comp: Option<&'a MetaComp>,
+}
+
+/// One rule module contributing part of the advice.
+pub trait Advisor: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Higher priorities win when modules disagree
+    fn priority(&self) -> i32 {
+        0
+    }
+
+    /// Advice from this module alone; fields it has no opinion on stay empty
+    fn advise(&self, ctx: &AdviceContext) -> Advice;
+}
+
+/// Runs a set of advisor modules and merges their advice.
+///
+/// Modules run from highest to lowest priority (ties in registration order)
+/// and the first opinion wins: econ, comp and the LLM insight are taken from
+/// the first module that sets them, shop and augment advice per slot,
+/// positioning advice per board hex, and item advice as long as its components
+/// were not already claimed by another item.
+pub struct AdvisorRegistry {
+    advisors: Vec<Box<dyn Advisor>>,
+}
+
+impl AdvisorRegistry {
+    /// A registry without any module
+    pub fn empty() -> Self {
+        Self {
+            advisors: Vec::new(),
+        }
+    }
+
+    pub fn register(&mut self, advisor: Box<dyn Advisor>) {
+        tracing::debug!(
+            "Registered advisor {} (priority {})",
+            advisor.name(),
+            advisor.priority()
+        );
+        self.advisors.push(advisor);
+    }
+
+    /// Names of the registered modules, in the order they run
+    pub fn names(&self) -> Vec<&str> {
+        self.ordered().iter().map(|a| a.name()).collect()
+    }
+
+    pub fn advise(&self, state: &GameState, data: &GameData) -> Advice {
+        let ctx = AdviceContext {
+            state,
+            data,
+            comp: best_comp(&data.meta_comps, state),
+        };
+
+        // Components on the item bench that items may still use
+        let mut components: HashMap<&str, u32> = HashMap::new();
+        for item in state.items.iter().filter(|i| i.is_component) {
+            *components.entry(item.id.as_str()).or_insert(0) += 1;
+        }
+
+        let mut merged = Advice::default();
+        for advisor in self.ordered() {
+            let advice = advisor.advise(&ctx);
+
+            for shop in advice.shop {
+                if !merged.shop.iter().any(|s| s.slot_index == shop.slot_index) {
+                    merged.shop.push(shop);
+                }
+            }
+            if merged.econ.is_none() {
+                merged.econ = advice.econ;
+            }
+            for item in advice.items {
+                if claim(&mut components, &item.item1, &item.item2) {
+                    merged.items.push(item);
+                }
+            }
+            if merged.comp.is_none() {
+                merged.comp = advice.comp;
+            }
+            for comp in advice.comps {
+                if !merged.comps.iter().any(|c| c.comp_name == comp.comp_name) {
+                    merged.comps.push(comp);
+                }
+            }
+            for positioning in advice.positioning {
+                if !merged.positioning.iter().any(|p| p.hex == positioning.hex) {
+                    merged.positioning.push(positioning);
+                }
+            }
+            for augment in advice.augments {
+                if !merged.augments.iter().any(|a| a.slot == augment.slot) {
+                    merged.augments.push(augment);
+                }
+            }
+            if merged.llm_insight.is_none() {
+                merged.llm_insight = advice.llm_insight;
+            }
+        }
+
+        merged.shop.sort_by_key(|s| s.slot_index);
+        merged.items.sort_by_key(|i| i.priority);
+        merged
+            .augments
+            .sort_by(|a, b| b.score.total_cmp(&a.score).then(a.slot.cmp(&b.slot)));
+        merged
+    }
+
+    fn ordered(&self) -> Vec<&dyn Advisor> {
+        let mut ordered: Vec<&dyn Advisor> = self.advisors.iter().map(|a| a.as_ref()).collect();
+        // Stable sort keeps registration order among equal priorities
+        ordered.sort_by_key(|a| std::cmp::Reverse(a.priority()));
+        ordered
+    }
+}
+
+impl Default for AdvisorRegistry {
+    /// The built-in econ, shop, item, comp, positioning and augment modules
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Box::new(EconAdvisor));
+        registry.register(Box::new(ShopAdvisor));
+        registry.register(Box::new(ItemAdvisor));
+        registry.register(Box::new(CompAdvisor));
+        registry.register(Box::new(PositioningAdvisor));
+        registry.register(Box::new(AugmentAdvisor));
+        registry
+    }
+}
+
+/// Take one of each component if both are left
+fn claim(components: &mut HashMap<&str, u32>, a: &str, b: &str) -> bool {
+    let available = |id: &str, n: u32| components.get(id).copied().unwrap_or(0) >= n;
+    let ok = if a == b {
+        available(a, 2)
+    } else {
+        available(a, 1) && available(b, 1)
+    };
+    if ok {
+        for id in [a, b] {
+            if let Some(n) = components.get_mut(id) {
+                *n -= 1;
+            }
+        }
+    }
+    ok
+}
+
+/// Interest, leveling and roll-down advice
+pub struct EconAdvisor;
+
+impl Advisor for EconAdvisor {
+    fn name(&self) -> &str {
+        "econ"
+    }
+
+    fn priority(&self) -> i32 {
+        ECON_PRIORITY
+    }
+
+    fn advise(&self, ctx: &AdviceContext) -> Advice {
+        Advice {
+            econ: econ_advice(ctx.state),
+            ..Default::default()
+        }
+    }
+}
+
+/// Buy/skip advice for the shop
+pub struct ShopAdvisor;
+
+impl Advisor for ShopAdvisor {
+    fn name(&self) -> &str {
+        "shop"
+    }
+
+    fn priority(&self) -> i32 {
+        SHOP_PRIORITY
+    }
+
+    fn advise(&self, ctx: &AdviceContext) -> Advice {
+        Advice {
+            shop: shop_advice(ctx.state, ctx.comp),
+            ..Default::default()
+        }
+    }
+}
+
+/// Which items to build from the held components
+pub struct ItemAdvisor;
+
+impl Advisor for ItemAdvisor {
+    fn name(&self) -> &str {
+        "items"
+    }
+
+    fn priority(&self) -> i32 {
+        ITEM_PRIORITY
+    }
+
+    fn advise(&self, ctx: &AdviceContext) -> Advice {
+        Advice {
+            items: item_advice(ctx.state, ctx.data, ctx.comp),
+            ..Default::default()
+        }
+    }
+}
+
+/// The closest meta comps
+pub struct CompAdvisor;
+
+impl Advisor for CompAdvisor {
+    fn name(&self) -> &str {
+        "comp"
+    }
+
+    fn priority(&self) -> i32 {
+        COMP_PRIORITY
+    }
+
+    fn advise(&self, ctx: &AdviceContext) -> Advice {
+        let comps = comp_advice(&ctx.data.meta_comps, ctx.state, TOP_COMPS);
+        Advice {
+            comp: comps.first().cloned(),
+            comps,
+            ..Default::default()
+        }
+    }
+}
+
+/// Where to put the units on the board
+pub struct PositioningAdvisor;
+
+impl Advisor for PositioningAdvisor {
+    fn name(&self) -> &str {
+        "positioning"
+    }
+
+    fn priority(&self) -> i32 {
+        POSITIONING_PRIORITY
+    }
+
+    fn advise(&self, ctx: &AdviceContext) -> Advice {
+        Advice {
+            positioning: positioning_advice(ctx.state, ctx.data, ctx.comp),
+            ..Default::default()
+        }
+    }
+}
+
+/// Which offered augment to pick
+pub struct AugmentAdvisor;
+
+impl Advisor for AugmentAdvisor {
+    fn name(&self) -> &str {
+        "augments"
+    }
+
+    fn priority(&self) -> i32 {
+        AUGMENT_PRIORITY
+    }
+
+    fn advise(&self, ctx: &AdviceContext) -> Advice {
+        Advice {
+            augments: augment_advice(ctx.state, ctx.data, ctx.comp),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AdviceTrace, EconAction, EconAdvice, ItemAdvice, ShopAction, ShopAdvice};
+    use tft_state::{Champion, Item, ShopSlot};
+
+    /// A house rule: always hold 50, never buy from slot 0, build one item
+    struct HouseRule {
+        priority: i32,
+    }
+
+    impl Advisor for HouseRule {
+        fn name(&self) -> &str {
+            "house"
+        }
+
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+
+        fn advise(&self, _ctx: &AdviceContext) -> Advice {
+            Advice {
+                econ: Some(EconAdvice {
+                    action: EconAction::Save,
+                    reason: "House rule: hold 50".to_string(),
+                    target_gold: Some(50),
+                    trace: AdviceTrace::new("house"),
+                }),
+                shop: vec![ShopAdvice {
+                    slot_index: 0,
+                    action: ShopAction::Skip,
+                    reason: "House rule".to_string(),
+                    trace: AdviceTrace::new("house"),
+                }],
+                items: vec![ItemAdvice {
+                    item1: "TFT_Item_BFSword".to_string(),
+                    item2: "TFT_Item_BFSword".to_string(),
+                    result: "TFT_Item_Deathblade".to_string(),
+                    priority: 5,
+                    reason: "House rule".to_string(),
+                    trace: AdviceTrace::new("house"),
+                }],
+                ..Default::default()
+            }
+        }
+    }
+
+    fn state() -> GameState {
+        let component = |id: &str| Item {
+            id: id.to_string(),
+            name: id.to_string(),
+            is_component: true,
+        };
+        GameState {
+            stage: "4-1".to_string(),
+            gold: 20,
+            level: 6,
+            hp: 30,
+            shop: vec![ShopSlot {
+                index: 0,
+                champion: Some(Champion {
+                    id: "Vi".to_string(),
+                    name: "Vi".to_string(),
+                    cost: 1,
+                    traits: Vec::new(),
+                }),
+                confidence: 0.9,
+            }],
+            items: vec![component("TFT_Item_BFSword"), component("TFT_Item_BFSword")],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_priorities_resolve_conflicts() {
+        let data = GameData::load(std::path::Path::new("/nonexistent")).unwrap();
+
+        let mut registry = AdvisorRegistry::default();
+        registry.register(Box::new(HouseRule { priority: 0 }));
+        let advice = registry.advise(&state(), &data);
+        // Built-in modules outrank the low-priority rule
+        assert_eq!(advice.econ.unwrap().action, EconAction::Roll);
+        assert_eq!(advice.shop.len(), 1);
+        assert_ne!(advice.shop[0].reason, "House rule");
+        // Both want the same two swords: only the first item gets them
+        assert_eq!(advice.items.len(), 1);
+        assert_ne!(advice.items[0].reason, "House rule");
+
+        let mut registry = AdvisorRegistry::default();
+        registry.register(Box::new(HouseRule { priority: 200 }));
+        assert_eq!(registry.names()[0], "house");
+        let advice = registry.advise(&state(), &data);
+        assert_eq!(advice.econ.unwrap().target_gold, Some(50));
+        assert_eq!(advice.shop[0].action, ShopAction::Skip);
+        assert_eq!(advice.items.len(), 1);
+        assert_eq!(advice.items[0].reason, "House rule");
+    }
+}
@@ -0,0 +1,387 @@
+use crate::{Advice, AdvisorRegistry, EconAction, ShopAction};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::path::Path;
+use tft_data::GameData;
+use tft_state::{GameState, RoundLedger, Timeline};
+
+/// What the player did in a round, from the economy ledger
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundActions {
+    pub rerolls: u32,
+    pub xp_bought: u32,
+    /// Champion IDs
+    pub units_bought: Vec<String>,
+}
+
+/// The advisor's view of one round next to what the player did
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundReview {
+    pub stage: String,
+    pub gold: u32,
+    pub level: u32,
+    pub hp: u32,
+    /// Advice for the state at the start of the round
+    pub advice: Advice,
+    /// `None` when the ledger has no record of the round
+    pub actions: Option<RoundActions>,
+    /// HP change until the start of the next reviewed round
+    pub hp_change: Option<i32>,
+    /// Where the player went against the advice, one line each
+    pub disagreements: Vec<String>,
+}
+
+/// "What the advisor would have done" over a finished match
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewReport {
+    pub rounds: Vec<RoundReview>,
+}
+
+/// Run the advisors on the state at the start of each round and compare
+/// their advice with the round's rerolls, XP and buys in the ledger.
+/// `snapshots` holds one state per round, in order.
+pub fn review_match(
+    snapshots: &[GameState],
+    ledger: &[RoundLedger],
+    advisors: &AdvisorRegistry,
+    data: &GameData,
+) -> ReviewReport {
+    let rounds = snapshots
+        .iter()
+        .enumerate()
+        .map(|(i, state)| {
+            let advice = advisors.advise(state, data);
+            let actions = ledger
+                .iter()
+                .find(|r| r.stage == state.stage)
+                .map(|r| RoundActions {
+                    rerolls: r.rerolls,
+                    xp_bought: r.xp_bought,
+                    units_bought: r.units_bought.clone(),
+                });
+            // HP reads 0 until the player list was recognised
+            let hp_change = snapshots
+                .get(i + 1)
+                .filter(|next| state.hp > 0 && next.hp > 0)
+                .map(|next| next.hp as i32 - state.hp as i32);
+            let disagreements = actions
+                .as_ref()
+                .map(|a| disagreements(state, &advice, a))
+                .unwrap_or_default();
+
+            RoundReview {
+                stage: state.stage.clone(),
+                gold: state.gold,
+                level: state.level,
+                hp: state.hp,
+                advice,
+                actions,
+                hp_change,
+                disagreements,
+            }
+        })
+        .collect();
+    ReviewReport { rounds }
+}
+
+/// Review a match saved to disk, from its timeline.json. The timeline only
+/// keeps the economy per frame, so the review covers econ advice; shop and
+/// board advice need the live states `review_match` gets from the pipeline.
+pub fn review_timeline(
+    path: &Path,
+    advisors: &AdvisorRegistry,
+    data: &GameData,
+) -> Result<ReviewReport> {
+    let timeline = Timeline::load(path)?;
+    let ledger = timeline.ledger();
+    Ok(review_match(
+        &timeline.round_states(),
+        ledger.rounds(),
+        advisors,
+        data,
+    ))
+}
+
+fn disagreements(state: &GameState, advice: &Advice, actions: &RoundActions) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    if let Some(econ) = &advice.econ {
+        let followed = match econ.action {
+            EconAction::Roll | EconAction::SlowRoll => actions.rerolls > 0,
+            EconAction::Level => actions.xp_bought > 0,
+            EconAction::Save => actions.rerolls == 0 && actions.xp_bought == 0,
+        };
+        if !followed {
+            lines.push(format!(
+                "Advisor said {} ({}); player {}",
+                action_name(econ.action),
+                econ.reason,
+                describe(actions)
+            ));
+        }
+    }
+
+    let mut bought = actions.units_bought.clone();
+    for shop in &advice.shop {
+        let slot = state.shop.iter().find(|s| s.index == shop.slot_index);
+        let Some(champion) = slot.and_then(|s| s.champion.as_ref()) else {
+            continue;
+        };
+        let position = bought.iter().position(|id| *id == champion.id);
+        match (shop.action, position) {
+            (ShopAction::Buy, None) => {
+                lines.push(format!("Missed buy: {} ({})", champion.name, shop.reason))
+            }
+            (ShopAction::Skip, Some(_)) => lines.push(format!(
+                "Bought {} against advice ({})",
+                champion.name, shop.reason
+            )),
+            _ => {}
+        }
+        // Each purchase answers one shop slot
+        if let Some(i) = position {
+            bought.remove(i);
+        }
+    }
+    lines
+}
+
+/// The action's name as the frontend and rule files spell it
+fn action_name(action: EconAction) -> String {
+    serde_json::to_value(action)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn describe(actions: &RoundActions) -> String {
+    let mut parts = Vec::new();
+    if actions.rerolls > 0 {
+        parts.push(format!("rolled {} times", actions.rerolls));
+    }
+    if actions.xp_bought > 0 {
+        parts.push(format!("bought XP {} times", actions.xp_bought));
+    }
+    if parts.is_empty() {
+        "saved".to_string()
+    } else {
+        parts.join(" and ")
+    }
+}
+
+impl ReviewReport {
+    /// Rounds where the player went against the advice
+    pub fn disagreeing(&self) -> impl Iterator<Item = &RoundReview> {
+        self.rounds.iter().filter(|r| !r.disagreements.is_empty())
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let reviewed = self.rounds.iter().filter(|r| r.actions.is_some()).count();
+        let mut md = String::from("# Advisor review\n\n");
+        let _ = writeln!(
+            md,
+            "{} of {} reviewed rounds went against the advice.",
+            self.disagreeing().count(),
+            reviewed
+        );
+
+        for round in self.disagreeing() {
+            let _ = write!(
+                md,
+                "\n## {}\n\n{} gold, level {}, {} HP",
+                round.stage, round.gold, round.level, round.hp
+            );
+            match round.hp_change {
+                Some(change) => {
+                    let _ = writeln!(md, " ({:+} HP by the next round)\n", change);
+                }
+                None => md.push_str("\n\n"),
+            }
+            if let Some(econ) = &round.advice.econ {
+                let _ = writeln!(
+                    md,
+                    "Advice: {} — {}\n",
+                    action_name(econ.action),
+                    econ.reason
+                );
+            }
+            for line in &round.disagreements {
+                let _ = writeln!(md, "- {}", line);
+            }
+        }
+        md
+    }
+
+    /// The markdown report as a standalone HTML page
+    pub fn to_html(&self) -> String {
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>Advisor review</title></head>\n<body>\n",
+        );
+        let mut in_list = false;
+        for line in self.to_markdown().lines() {
+            let is_item = line.starts_with("- ");
+            if in_list && !is_item {
+                html.push_str("</ul>\n");
+                in_list = false;
+            }
+            if let Some(title) = line.strip_prefix("# ") {
+                let _ = writeln!(html, "<h1>{}</h1>", escape_html(title));
+            } else if let Some(title) = line.strip_prefix("## ") {
+                let _ = writeln!(html, "<h2>{}</h2>", escape_html(title));
+            } else if let Some(item) = line.strip_prefix("- ") {
+                if !in_list {
+                    html.push_str("<ul>\n");
+                    in_list = true;
+                }
+                let _ = writeln!(html, "<li>{}</li>", escape_html(item));
+            } else if !line.is_empty() {
+                let _ = writeln!(html, "<p>{}</p>", escape_html(line));
+            }
+        }
+        if in_list {
+            html.push_str("</ul>\n");
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    /// Write review.json, review.md and review.html into `dir`
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        for (name, content) in [
+            ("review.json", json),
+            ("review.md", self.to_markdown()),
+            ("review.html", self.to_html()),
+        ] {
+            let path = dir.join(name);
+            std::fs::write(&path, content)
+                .with_context(|| format!("Failed to write review to {}", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tft_state::{BoardSlot, Champion, ShopSlot, TimelineEntry};
+
+    fn champion(id: &str) -> Champion {
+        Champion {
+            id: id.to_string(),
+            name: id.to_string(),
+            cost: 2,
+            traits: Vec::new(),
+        }
+    }
+
+    fn state(stage: &str, gold: u32, level: u32, hp: u32) -> GameState {
+        GameState {
+            stage: stage.to_string(),
+            gold,
+            level,
+            hp,
+            ..Default::default()
+        }
+    }
+
+    fn ledger(stage: &str, rerolls: u32, xp_bought: u32) -> RoundLedger {
+        RoundLedger {
+            stage: stage.to_string(),
+            rerolls,
+            xp_bought,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_review_lists_disagreements() {
+        let data = GameData::load(Path::new("/nonexistent")).unwrap();
+        // Behind on levels with a third Vi in the shop
+        let mut first = state("3-2", 34, 5, 80);
+        first.bench = (0..2)
+            .map(|col| BoardSlot {
+                row: 0,
+                col,
+                champion: Some(champion("Vi")),
+                star_level: 1,
+                items: Vec::new(),
+            })
+            .collect();
+        first.shop = vec![ShopSlot {
+            index: 0,
+            champion: Some(champion("Vi")),
+            confidence: 0.9,
+        }];
+        let snapshots = vec![first, state("3-3", 26, 6, 68)];
+
+        let report = review_match(
+            &snapshots,
+            &[ledger("3-2", 2, 0), ledger("3-3", 0, 0)],
+            &AdvisorRegistry::default(),
+            &data,
+        );
+        assert_eq!(report.rounds.len(), 2);
+        let round = &report.rounds[0];
+        assert_eq!(round.hp_change, Some(-12));
+        assert_eq!(round.disagreements.len(), 2);
+        assert!(round.disagreements[0].starts_with("Advisor said level"));
+        assert!(round.disagreements[0].ends_with("player rolled 2 times"));
+        assert!(round.disagreements[1].starts_with("Missed buy: Vi"));
+        // Saving as advised is no disagreement
+        assert!(report.rounds[1].disagreements.is_empty());
+
+        let md = report.to_markdown();
+        assert!(md.contains("1 of 2 reviewed rounds"));
+        assert!(md.contains("## 3-2\n\n34 gold, level 5, 80 HP (-12 HP by the next round)"));
+        assert!(!md.contains("## 3-3"));
+
+        let html = report.to_html();
+        assert!(html.contains("<h2>3-2</h2>"));
+        assert!(html.contains("<ul>\n<li>Advisor said level"));
+    }
+
+    #[test]
+    fn test_review_saved_timeline() {
+        let mut timeline = Timeline::default();
+        for (timestamp_ms, stage, gold, level) in [(1000, "3-2", 34, 5), (2000, "3-3", 26, 6)] {
+            timeline.push(TimelineEntry {
+                timestamp_ms,
+                stage: Some(stage.to_string()),
+                gold: Some(gold),
+                level: Some(level),
+                hp: Some(70),
+            });
+        }
+        let dir =
+            std::env::temp_dir().join(format!("tft_advisor_test_review_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("timeline.json");
+        timeline.save(&path).unwrap();
+        let data = GameData::load(Path::new("/nonexistent")).unwrap();
+        let report = review_timeline(&path, &AdvisorRegistry::default(), &data);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let report = report.unwrap();
+        let stages: Vec<&str> = report.rounds.iter().map(|r| r.stage.as_str()).collect();
+        assert_eq!(stages, vec!["3-2", "3-3"]);
+        assert!(report.rounds[0].advice.econ.is_some());
+        assert!(review_timeline(
+            Path::new("/nonexistent/timeline.json"),
+            &AdvisorRegistry::default(),
+            &data
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,793 @@
+use crate::{
+    Advice, AdviceContext, AdviceTrace, Advisor, EconAction, EconAdvice, ShopAction, ShopAdvice,
+};
+use serde::Deserialize;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use tft_state::{parse_stage, GameState};
+
+/// Rule file name in the data directory
+pub const RULES_FILE: &str = "rules.json";
+
+/// Default priority of the rule file: above the built-in modules, so coaches'
+/// rules override them
+const DEFAULT_RULES_PRIORITY: i32 = 150;
+
+/// How often the rule file is checked for changes
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A problem in the rule file, pointing at the offending rule when there is one
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleError {
+    /// Position of the rule in the file, from 0
+    pub index: Option<usize>,
+    pub name: Option<String>,
+    pub message: String,
+}
+
+impl std::fmt::Display for RuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.index, &self.name) {
+            (Some(i), Some(name)) => write!(f, "rule #{} ({}): {}", i + 1, name, self.message),
+            (Some(i), None) => write!(f, "rule #{}: {}", i + 1, self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+/// A value read from the game state for a condition
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+enum Value {
+    Number(f64),
+    Stage((u32, u32)),
+}
+
+impl Value {
+    fn to_json(self) -> serde_json::Value {
+        match self {
+            Value::Number(n) if n.fract() == 0.0 => (n as i64).into(),
+            Value::Number(n) => n.into(),
+            Value::Stage((stage, round)) => format!("{}-{}", stage, round).into(),
+        }
+    }
+}
+
+/// State fields and derived metrics conditions can test
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Gold,
+    Level,
+    Hp,
+    Streak,
+    WinStreak,
+    LossStreak,
+    Stage,
+    Round,
+    Interest,
+    BenchUnits,
+    BoardUnits,
+    Components,
+}
+
+const FIELDS: [(&str, Field); 12] = [
+    ("gold", Field::Gold),
+    ("level", Field::Level),
+    ("hp", Field::Hp),
+    ("streak", Field::Streak),
+    ("winStreak", Field::WinStreak),
+    ("lossStreak", Field::LossStreak),
+    ("stage", Field::Stage),
+    ("round", Field::Round),
+    ("interest", Field::Interest),
+    ("benchUnits", Field::BenchUnits),
+    ("boardUnits", Field::BoardUnits),
+    ("components", Field::Components),
+];
+
+impl Field {
+    fn name(self) -> &'static str {
+        FIELDS
+            .iter()
+            .find(|(_, f)| *f == self)
+            .map(|(name, _)| *name)
+            .unwrap_or("?")
+    }
+
+    /// Value in `state`, `None` while it has not been read
+    fn value(self, state: &GameState) -> Option<Value> {
+        let n = |v: f64| Some(Value::Number(v));
+        let units = |slots: &[tft_state::BoardSlot]| {
+            slots.iter().filter(|s| s.champion.is_some()).count() as f64
+        };
+        match self {
+            Field::Stage => parse_stage(&state.stage).map(Value::Stage),
+            _ if state.stage.is_empty() => None,
+            // HP reads 0 until the player list was recognised
+            Field::Hp if state.hp == 0 => None,
+            Field::Hp => n(state.hp as f64),
+            Field::Gold => n(state.gold as f64),
+            Field::Level => n(state.level as f64),
+            Field::Streak => n(state.streak as f64),
+            Field::WinStreak => n(state.streak.max(0) as f64),
+            Field::LossStreak => n((-state.streak).max(0) as f64),
+            Field::Round => n(state.round as f64),
+            Field::Interest => n((state.gold / 10).min(5) as f64),
+            Field::BenchUnits => n(units(&state.bench)),
+            Field::BoardUnits => n(units(&state.board)),
+            Field::Components => n(state.items.iter().filter(|i| i.is_component).count() as f64),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+impl Op {
+    fn parse(op: &str) -> Option<Self> {
+        Some(match op {
+            "<" => Op::Lt,
+            "<=" => Op::Le,
+            ">" => Op::Gt,
+            ">=" => Op::Ge,
+            "==" => Op::Eq,
+            "!=" => Op::Ne,
+            _ => return None,
+        })
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+            Op::Eq => "==",
+            Op::Ne => "!=",
+        }
+    }
+
+    fn test(self, a: Value, b: Value) -> bool {
+        match self {
+            Op::Lt => a < b,
+            Op::Le => a <= b,
+            Op::Gt => a > b,
+            Op::Ge => a >= b,
+            Op::Eq => a == b,
+            Op::Ne => a != b,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Condition {
+    field: Field,
+    op: Op,
+    value: Value,
+}
+
+impl Condition {
+    fn matches(&self, state: &GameState) -> bool {
+        self.field
+            .value(state)
+            .is_some_and(|v| self.op.test(v, self.value))
+    }
+}
+
+/// Which shop slots a shop rule applies to
+#[derive(Debug, Clone, Default)]
+struct ShopFilter {
+    cost: Option<u32>,
+    /// Champion ID or display name
+    champion: Option<String>,
+    /// Whether a copy is already on the bench or board
+    owned: Option<bool>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    name: String,
+    when: Vec<Condition>,
+    econ: Option<EconAdvice>,
+    shop: Option<(ShopFilter, ShopAction, String)>,
+}
+
+impl Rule {
+    /// Trace of a matching rule: its fields' values and the conditions they met
+    fn trace(&self, state: &GameState) -> AdviceTrace {
+        let mut trace = AdviceTrace::new(&format!("rules.{}", self.name));
+        for condition in &self.when {
+            let name = condition.field.name();
+            if !trace.inputs.iter().any(|i| i.name == name) {
+                let value = condition.field.value(state).map(Value::to_json);
+                trace = trace.input(name, value);
+            }
+            trace.check(name, condition.op.symbol(), condition.value.to_json(), true);
+        }
+        trace
+    }
+}
+
+/// A parsed and validated rule file.
+///
+/// Rules are checked in file order. The first matching rule with `econ` sets
+/// the econ advice; for each shop slot, the first matching rule with a `shop`
+/// action whose filter fits the slot sets its advice. Reasons may name state
+/// fields in braces, e.g. "{hp} HP left".
+#[derive(Debug, Clone)]
+pub struct RuleSet {
+    priority: i32,
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawRuleFile {
+    #[allow(dead_code)]
+    version: String,
+    priority: Option<i32>,
+    rules: Vec<RawRule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawRule {
+    name: Option<String>,
+    #[serde(default)]
+    when: Vec<RawCondition>,
+    econ: Option<RawEcon>,
+    shop: Option<RawShop>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawCondition {
+    field: String,
+    op: String,
+    value: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+struct RawEcon {
+    action: String,
+    reason: String,
+    target_gold: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawShop {
+    action: String,
+    reason: String,
+    cost: Option<u32>,
+    champion: Option<String>,
+    owned: Option<bool>,
+}
+
+impl RuleSet {
+    /// Parse and validate a rule file. Every invalid rule is reported.
+    pub fn parse(json: &str) -> Result<Self, Vec<RuleError>> {
+        let file: RawRuleFile = serde_json::from_str(json).map_err(|e| {
+            vec![RuleError {
+                index: None,
+                name: None,
+                message: format!("invalid rule file: {}", e),
+            }]
+        })?;
+
+        let mut rules = Vec::new();
+        let mut errors = Vec::new();
+        for (index, raw) in file.rules.into_iter().enumerate() {
+            let name = raw.name.clone();
+            match validate(raw, index) {
+                Ok(rule) => rules.push(rule),
+                Err(messages) => errors.extend(messages.into_iter().map(|message| RuleError {
+                    index: Some(index),
+                    name: name.clone(),
+                    message,
+                })),
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(Self {
+            priority: file.priority.unwrap_or(DEFAULT_RULES_PRIORITY),
+            rules,
+        })
+    }
+
+    /// Read and parse a rule file
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse_file(path, &content)
+    }
+
+    /// `parse`, with the errors reported against `path`
+    fn parse_file(path: &Path, content: &str) -> anyhow::Result<Self> {
+        Self::parse(content).map_err(|errors| {
+            let lines: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+            anyhow::anyhow!("Invalid rules in {}:\n{}", path.display(), lines.join("\n"))
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Apply the rules to a game state
+    pub fn evaluate(&self, state: &GameState) -> Advice {
+        let mut advice = Advice::default();
+        let matching = self
+            .rules
+            .iter()
+            .filter(|r| r.when.iter().all(|c| c.matches(state)));
+
+        for rule in matching {
+            if let (None, Some(econ)) = (&advice.econ, &rule.econ) {
+                tracing::debug!("Rule {} sets econ advice", rule.name);
+                advice.econ = Some(EconAdvice {
+                    reason: fill_reason(&econ.reason, state),
+                    trace: rule.trace(state),
+                    ..econ.clone()
+                });
+            }
+            let Some((filter, action, reason)) = &rule.shop else {
+                continue;
+            };
+            for slot in &state.shop {
+                let Some(champion) = &slot.champion else {
+                    continue;
+                };
+                let taken = advice.shop.iter().any(|s| s.slot_index == slot.index);
+                if taken || !filter.fits(champion, state) {
+                    continue;
+                }
+                advice.shop.push(ShopAdvice {
+                    slot_index: slot.index,
+                    action: *action,
+                    reason: fill_reason(reason, state),
+                    trace: rule.trace(state),
+                });
+            }
+        }
+        advice.shop.sort_by_key(|s| s.slot_index);
+        advice
+    }
+}
+
+impl ShopFilter {
+    fn fits(&self, champion: &tft_state::Champion, state: &GameState) -> bool {
+        let owned = state
+            .bench
+            .iter()
+            .chain(&state.board)
+            .any(|s| s.champion.as_ref().is_some_and(|c| c.id == champion.id));
+        self.cost.is_none_or(|cost| champion.cost == cost)
+            && self.owned.is_none_or(|o| o == owned)
+            && self
+                .champion
+                .as_ref()
+                .is_none_or(|c| *c == champion.id || c.eq_ignore_ascii_case(champion.name.trim()))
+    }
+}
+
+/// Check one raw rule, collecting every problem in it
+fn validate(raw: RawRule, index: usize) -> Result<Rule, Vec<String>> {
+    let mut errors = Vec::new();
+
+    let mut when = Vec::new();
+    for (i, c) in raw.when.iter().enumerate() {
+        match validate_condition(c) {
+            Ok(condition) => when.push(condition),
+            Err(e) => errors.push(format!("condition #{}: {}", i + 1, e)),
+        }
+    }
+
+    if raw.econ.is_none() && raw.shop.is_none() {
+        errors.push("needs an \"econ\" or \"shop\" action".to_string());
+    }
+
+    let econ = raw.econ.and_then(|e| {
+        let action = match e.action.to_lowercase().as_str() {
+            "level" => EconAction::Level,
+            "roll" => EconAction::Roll,
+            "save" => EconAction::Save,
+            "slow-roll" | "slowroll" => EconAction::SlowRoll,
+            other => {
+                errors.push(format!(
+                    "unknown econ action '{}' (expected level, roll, save or slow-roll)",
+                    other
+                ));
+                return None;
+            }
+        };
+        if e.reason.trim().is_empty() {
+            errors.push("econ reason is empty".to_string());
+        }
+        Some(EconAdvice {
+            action,
+            reason: e.reason,
+            target_gold: e.target_gold,
+            trace: AdviceTrace::default(),
+        })
+    });
+
+    let shop = raw.shop.and_then(|s| {
+        let action = match s.action.to_lowercase().as_str() {
+            "buy" => ShopAction::Buy,
+            "skip" => ShopAction::Skip,
+            "consider" => ShopAction::Consider,
+            other => {
+                errors.push(format!(
+                    "unknown shop action '{}' (expected buy, skip or consider)",
+                    other
+                ));
+                return None;
+            }
+        };
+        if s.reason.trim().is_empty() {
+            errors.push("shop reason is empty".to_string());
+        }
+        let filter = ShopFilter {
+            cost: s.cost,
+            champion: s.champion,
+            owned: s.owned,
+        };
+        Some((filter, action, s.reason))
+    });
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(Rule {
+        name: raw.name.unwrap_or_else(|| format!("rule #{}", index + 1)),
+        when,
+        econ,
+        shop,
+    })
+}
+
+fn validate_condition(raw: &RawCondition) -> Result<Condition, String> {
+    let field = FIELDS
+        .iter()
+        .find(|(name, _)| *name == raw.field)
+        .map(|(_, field)| *field)
+        .ok_or_else(|| {
+            let names: Vec<&str> = FIELDS.iter().map(|(name, _)| *name).collect();
+            format!(
+                "unknown field '{}' (expected one of {})",
+                raw.field,
+                names.join(", ")
+            )
+        })?;
+    let op = Op::parse(&raw.op).ok_or_else(|| {
+        format!(
+            "unknown operator '{}' (expected <, <=, >, >=, == or !=)",
+            raw.op
+        )
+    })?;
+    let value = match field {
+        Field::Stage => raw
+            .value
+            .as_str()
+            .and_then(parse_stage)
+            .map(Value::Stage)
+            .ok_or_else(|| format!("stage value must look like \"3-2\", got {}", raw.value))?,
+        _ => raw
+            .value
+            .as_f64()
+            .map(Value::Number)
+            .ok_or_else(|| format!("'{}' needs a number, got {}", raw.field, raw.value))?,
+    };
+    Ok(Condition { field, op, value })
+}
+
+/// Replace "{field}" placeholders with the state's values
+fn fill_reason(reason: &str, state: &GameState) -> String {
+    let mut filled = reason.to_string();
+    for (name, field) in FIELDS {
+        let placeholder = format!("{{{}}}", name);
+        if !filled.contains(&placeholder) {
+            continue;
+        }
+        let value = match field.value(state) {
+            Some(Value::Number(n)) => n.to_string(),
+            Some(Value::Stage(_)) => state.stage.clone(),
+            None => "?".to_string(),
+        };
+        filled = filled.replace(&placeholder, &value);
+    }
+    filled
+}
+
+/// Advisor module backed by the rule file in the data directory, reloaded when
+/// the file changes. An invalid edit is logged and the previous rules are kept.
+pub struct RuleAdvisor {
+    path: PathBuf,
+    loaded: Mutex<LoadedRules>,
+}
+
+struct LoadedRules {
+    rules: Option<RuleSet>,
+    /// Version of the file last looked at, `None` while it is missing
+    version: Option<FileVersion>,
+    checked: Option<Instant>,
+}
+
+/// Identifies one version of the rule file. Two writes can land within the
+/// same modification time tick, so the length and a content hash count too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileVersion {
+    modified: Option<SystemTime>,
+    len: usize,
+    hash: u64,
+}
+
+impl FileVersion {
+    fn of(modified: Option<SystemTime>, content: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        Self {
+            modified,
+            len: content.len(),
+            hash: hasher.finish(),
+        }
+    }
+}
+
+impl RuleAdvisor {
+    /// Watch `data_dir/rules.json`. A missing file means no rules until it appears.
+    pub fn new(data_dir: &Path) -> Self {
+        let advisor = Self {
+            path: data_dir.join(RULES_FILE),
+            loaded: Mutex::new(LoadedRules {
+                rules: None,
+                version: None,
+                checked: None,
+            }),
+        };
+        advisor.reload_if_changed();
+        advisor
+    }
+
+    /// Reload the rule file if it changed. Returns whether new rules were loaded.
+    pub fn reload_if_changed(&self) -> bool {
+        let Ok(mut loaded) = self.loaded.lock() else {
+            return false;
+        };
+        loaded.checked = Some(Instant::now());
+
+        let Ok(content) = std::fs::read_to_string(&self.path) else {
+            loaded.version = None;
+            if loaded.rules.take().is_some() {
+                tracing::info!("Rule file {} removed", self.path.display());
+            }
+            return false;
+        };
+        let modified = std::fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .ok();
+        let version = FileVersion::of(modified, &content);
+        if loaded.version == Some(version) {
+            return false;
+        }
+        loaded.version = Some(version);
+
+        match RuleSet::parse_file(&self.path, &content) {
+            Ok(rules) => {
+                tracing::info!(
+                    "Loaded {} advice rule(s) from {}",
+                    rules.len(),
+                    self.path.display()
+                );
+                loaded.rules = Some(rules);
+                true
+            }
+            Err(e) => {
+                tracing::warn!("{:#}. Keeping the previous rules.", e);
+                false
+            }
+        }
+    }
+
+    fn poll(&self) {
+        let due = self
+            .loaded
+            .lock()
+            .map(|l| l.checked.is_none_or(|t| t.elapsed() >= POLL_INTERVAL))
+            .unwrap_or(false);
+        if due {
+            self.reload_if_changed();
+        }
+    }
+}
+
+impl Advisor for RuleAdvisor {
+    fn name(&self) -> &str {
+        "rules"
+    }
+
+    fn priority(&self) -> i32 {
+        self.loaded
+            .lock()
+            .ok()
+            .and_then(|l| l.rules.as_ref().map(|r| r.priority))
+            .unwrap_or(DEFAULT_RULES_PRIORITY)
+    }
+
+    fn advise(&self, ctx: &AdviceContext) -> Advice {
+        self.poll();
+        let Ok(loaded) = self.loaded.lock() else {
+            return Advice::default();
+        };
+        loaded
+            .rules
+            .as_ref()
+            .map(|rules| rules.evaluate(ctx.state))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tft_state::{Champion, ShopSlot};
+
+    const RULES: &str = r#"{
+        "version": "1",
+        "rules": [
+            {
+                "name": "panic roll",
+                "when": [
+                    {"field": "hp", "op": "<=", "value": 30},
+                    {"field": "stage", "op": ">=", "value": "4-1"}
+                ],
+                "econ": {"action": "roll", "reason": "{hp} HP at {stage}", "targetGold": 0}
+            },
+            {
+                "name": "no 5-costs early",
+                "when": [{"field": "stage", "op": "<", "value": "4-1"}],
+                "shop": {"action": "skip", "reason": "Too early for 5-costs", "cost": 5}
+            },
+            {
+                "econ": {"action": "save", "reason": "Hold {gold}", "targetGold": 50}
+            }
+        ]
+    }"#;
+
+    fn state(stage: &str, hp: u32) -> GameState {
+        GameState {
+            stage: stage.to_string(),
+            gold: 34,
+            level: 7,
+            hp,
+            shop: [(0, 5), (1, 2)]
+                .into_iter()
+                .map(|(index, cost)| ShopSlot {
+                    index,
+                    champion: Some(Champion {
+                        id: format!("Unit{}", index),
+                        name: format!("Unit{}", index),
+                        cost,
+                        traits: Vec::new(),
+                    }),
+                    confidence: 0.9,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_and_evaluate() {
+        let rules = RuleSet::parse(RULES).unwrap();
+        assert_eq!(rules.len(), 3);
+
+        let advice = rules.evaluate(&state("4-2", 25));
+        let econ = advice.econ.unwrap();
+        assert_eq!(econ.action, EconAction::Roll);
+        assert_eq!(econ.reason, "25 HP at 4-2");
+        assert_eq!(
+            econ.trace.summary(),
+            "rules.panic roll (hp=25, stage=4-2; hp <= 30, stage >= 4-1)"
+        );
+        assert!(advice.shop.is_empty());
+
+        // Later rules fill in when earlier ones don't match; unknown HP never matches
+        let advice = rules.evaluate(&state("3-2", 0));
+        assert_eq!(advice.econ.unwrap().reason, "Hold 34");
+        assert_eq!(advice.shop.len(), 1);
+        assert_eq!(advice.shop[0].slot_index, 0);
+        assert_eq!(advice.shop[0].action, ShopAction::Skip);
+    }
+
+    #[test]
+    fn test_validation_errors_point_to_rules() {
+        let errors = RuleSet::parse(
+            r#"{"version": "1", "rules": [
+                {"name": "ok", "econ": {"action": "save", "reason": "x"}},
+                {"name": "typo", "when": [{"field": "gld", "op": ">", "value": 10}],
+                 "econ": {"action": "hoard", "reason": "x"}},
+                {"when": [{"field": "stage", "op": "=>", "value": "4-1"}]}
+            ]}"#,
+        )
+        .unwrap_err();
+
+        let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(messages.len(), 4, "{:?}", messages);
+        assert!(messages[0].starts_with("rule #2 (typo): condition #1: unknown field 'gld'"));
+        assert!(messages[1].starts_with("rule #2 (typo): unknown econ action 'hoard'"));
+        assert!(messages[2].starts_with("rule #3: condition #1: unknown operator '=>'"));
+        assert_eq!(messages[3], "rule #3: needs an \"econ\" or \"shop\" action");
+
+        let syntax = RuleSet::parse("{\"version\": \"1\", \"rules\": [").unwrap_err();
+        assert!(syntax[0].index.is_none());
+    }
+
+    #[test]
+    fn test_hot_reload() {
+        let dir =
+            std::env::temp_dir().join(format!("tft_advisor_test_rules_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(RULES_FILE);
+
+        let advisor = RuleAdvisor::new(&dir);
+        let data = tft_data::GameData::default();
+        let state = state("4-2", 25);
+        let ctx = AdviceContext {
+            state: &state,
+            data: &data,
+            comp: None,
+        };
+        assert!(advisor.advise(&ctx).econ.is_none());
+
+        let write = |content: &str, secs: u64| {
+            std::fs::write(&path, content).unwrap();
+            let file = std::fs::File::options().write(true).open(&path).unwrap();
+            file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+                .unwrap();
+        };
+
+        write(RULES, 1_000);
+        assert!(advisor.reload_if_changed());
+        assert_eq!(advisor.advise(&ctx).econ.unwrap().action, EconAction::Roll);
+        assert!(!advisor.reload_if_changed());
+
+        // A broken edit keeps the previous rules
+        write("{\"version\": \"1\", \"rules\": [{}]}", 2_000);
+        assert!(!advisor.reload_if_changed());
+        assert_eq!(advisor.advise(&ctx).econ.unwrap().action, EconAction::Roll);
+
+        // A fix written within the same modification time is still seen
+        write(
+            r#"{"version": "1", "priority": 10, "rules": [
+                {"econ": {"action": "level", "reason": "Level up"}}
+            ]}"#,
+            2_000,
+        );
+        assert!(advisor.reload_if_changed());
+        assert_eq!(advisor.priority(), 10);
+        assert_eq!(advisor.advise(&ctx).econ.unwrap().action, EconAction::Level);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,260 @@
+use crate::comp::{held_units, unit_matches};
+use crate::econ::breakpoint_at_or_below;
+use crate::{AdviceTrace, ShopAction, ShopAdvice};
+use tft_data::MetaComp;
+use tft_state::{parse_stage, Champion, GameState};
+
+/// Up to this stage, pairs and early-game units are worth holding
+const EARLY_STAGE: u32 = 2;
+
+/// Buy/skip advice for each filled shop slot, given the board, the comp being
+/// played, and the gold left and copies gained by the earlier recommended buys.
+pub fn shop_advice(state: &GameState, comp: Option<&MetaComp>) -> Vec<ShopAdvice> {
+    let stage = parse_stage(&state.stage)
+        .map(|(stage, _)| stage)
+        .unwrap_or(0);
+    let held = held_units(state);
+    let mut gold = state.gold;
+    // Champion IDs recommended for purchase in earlier slots
+    let mut bought: Vec<&str> = Vec::new();
+    let mut advice = Vec::new();
+
+    for slot in &state.shop {
+        let Some(champion) = &slot.champion else {
+            continue;
+        };
+        let bought_copies = bought.iter().filter(|id| **id == champion.id).count();
+        let (action, reason, trace) =
+            slot_advice(champion, &held, bought_copies, state, comp, stage, gold);
+        if action == ShopAction::Buy {
+            gold -= champion.cost;
+            bought.push(&champion.id);
+        }
+        advice.push(ShopAdvice {
+            slot_index: slot.index,
+            action,
+            reason,
+            trace,
+        });
+    }
+    advice
+}
+
+fn slot_advice(
+    champion: &Champion,
+    held: &[&Champion],
+    bought_copies: usize,
+    state: &GameState,
+    comp: Option<&MetaComp>,
+    stage: u32,
+    gold: u32,
+) -> (ShopAction, String, AdviceTrace) {
+    let mut trace = AdviceTrace::default()
+        .input("champion", champion.id.as_str())
+        .input("cost", champion.cost)
+        .input("gold", gold)
+        .input("stage", stage)
+        .input("comp", comp.map(|c| c.name.as_str()));
+
+    if !trace.check("cost", "<=", gold, champion.cost <= gold) {
+        return (
+            ShopAction::Skip,
+            format!("Can't afford {}", champion.name),
+            trace.fired("shop.unaffordable"),
+        );
+    }
+
+    let one_stars = state
+        .bench
+        .iter()
+        .chain(&state.board)
+        .filter(|s| s.star_level <= 1)
+        .filter(|s| s.champion.as_ref().is_some_and(|c| c.id == champion.id))
+        .count()
+        + bought_copies;
+    trace = trace.input("oneStarCopies", one_stars);
+    if trace.check("oneStarCopies % 3", "==", 2, one_stars % 3 == 2) {
+        return (
+            ShopAction::Buy,
+            format!("Third copy upgrades {} to 2-star", champion.name),
+            trace.fired("shop.upgrade"),
+        );
+    }
+
+    let owned = bought_copies > 0 || held.iter().any(|c| c.id == champion.id);
+    let in_comp = |units: &[String]| units.iter().any(|u| unit_matches(u, champion));
+    let (action, reason, rule) = match comp {
+        Some(comp) if in_comp(&comp.core_units) => (
+            ShopAction::Buy,
+            format!("Core unit of {}", comp.name),
+            "shop.core-unit",
+        ),
+        Some(comp) if in_comp(&comp.flex_units) => (
+            ShopAction::Consider,
+            format!("Flex unit of {}", comp.name),
+            "shop.flex-unit",
+        ),
+        Some(comp) if stage <= EARLY_STAGE && in_comp(&comp.early_game) => (
+            ShopAction::Buy,
+            format!("Early-game hold for {}", comp.name),
+            "shop.early-game",
+        ),
+        _ if owned && stage <= EARLY_STAGE => (
+            ShopAction::Buy,
+            format!("Pair toward a 2-star {}", champion.name),
+            "shop.pair",
+        ),
+        _ if owned => (
+            ShopAction::Consider,
+            format!("Pair toward a 2-star {}", champion.name),
+            "shop.pair",
+        ),
+        Some(comp) => (
+            ShopAction::Skip,
+            format!("Not part of {}", comp.name),
+            "shop.off-comp",
+        ),
+        None if stage <= EARLY_STAGE && champion.cost <= 2 => (
+            ShopAction::Consider,
+            "Cheap early-game unit to fill the board".to_string(),
+            "shop.early-filler",
+        ),
+        None => (
+            ShopAction::Skip,
+            "Doesn't upgrade anything".to_string(),
+            "shop.no-upgrade",
+        ),
+    };
+    trace = trace.input("owned", owned);
+
+    // Non-upgrades should not cost an interest breakpoint once the econ matters
+    let breakpoint = breakpoint_at_or_below(gold);
+    if action == ShopAction::Buy
+        && stage > EARLY_STAGE
+        && trace.check(
+            "gold - cost",
+            "<",
+            breakpoint,
+            gold - champion.cost < breakpoint,
+        )
+    {
+        return (
+            ShopAction::Consider,
+            format!("{}, but drops you below {} gold", reason, breakpoint),
+            trace.fired("shop.interest-breakpoint"),
+        );
+    }
+    (action, reason, trace.fired(rule))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tft_state::{BoardSlot, ShopSlot};
+
+    fn champion(id: &str, cost: u32) -> Champion {
+        Champion {
+            id: id.to_string(),
+            name: id.to_string(),
+            cost,
+            traits: Vec::new(),
+        }
+    }
+
+    fn unit(id: &str, star_level: u32) -> BoardSlot {
+        BoardSlot {
+            row: 0,
+            col: 0,
+            champion: Some(champion(id, 2)),
+            star_level,
+            items: Vec::new(),
+        }
+    }
+
+    fn comp() -> MetaComp {
+        MetaComp {
+            name: "Jinx Reroll".to_string(),
+            tier: "S".to_string(),
+            core_units: vec!["Jinx".to_string(), "Vi".to_string()],
+            flex_units: vec!["Ekko".to_string()],
+            core_items: Default::default(),
+            early_game: vec!["Poppy".to_string()],
+            power_spike: "3-2".to_string(),
+        }
+    }
+
+    fn state(stage: &str, gold: u32, shop: &[(&str, u32)]) -> GameState {
+        GameState {
+            stage: stage.to_string(),
+            gold,
+            shop: shop
+                .iter()
+                .enumerate()
+                .map(|(index, (id, cost))| ShopSlot {
+                    index,
+                    champion: Some(champion(id, *cost)),
+                    confidence: 0.9,
+                })
+                .collect(),
+            bench: vec![unit("Ahri", 1), unit("Ahri", 1), unit("Vi", 1)],
+            ..Default::default()
+        }
+    }
+
+    fn actions(advice: &[ShopAdvice]) -> Vec<ShopAction> {
+        advice.iter().map(|a| a.action).collect()
+    }
+
+    #[test]
+    fn test_upgrades_and_comp_units() {
+        // The first Ahri already completes the 2-star
+        let doubled = state("3-3", 40, &[("Ahri", 3), ("Ahri", 3)]);
+        let advice = shop_advice(&doubled, Some(&comp()));
+        assert_eq!(advice[0].trace.rule, "shop.upgrade");
+        assert_ne!(advice[1].trace.rule, "shop.upgrade");
+
+        let state = state(
+            "3-3",
+            40,
+            &[("Ahri", 3), ("Jinx", 2), ("Ekko", 3), ("Zed", 4), ("Vi", 2)],
+        );
+        let advice = shop_advice(&state, Some(&comp()));
+        assert_eq!(
+            actions(&advice),
+            vec![
+                ShopAction::Buy,
+                ShopAction::Buy,
+                ShopAction::Consider,
+                ShopAction::Skip,
+                ShopAction::Buy,
+            ]
+        );
+        assert_eq!(advice[0].reason, "Third copy upgrades Ahri to 2-star");
+
+        // Without a comp, a pair is only worth a look past the early game
+        let advice = shop_advice(&state, None);
+        assert_eq!(advice[4].action, ShopAction::Consider);
+        assert_eq!(advice[3].action, ShopAction::Skip);
+    }
+
+    #[test]
+    fn test_gold_and_interest() {
+        // 31 gold: the core unit would drop below 30, the upgrade still goes
+        let advice = shop_advice(
+            &state("4-1", 31, &[("Jinx", 2), ("Ahri", 3)]),
+            Some(&comp()),
+        );
+        assert_eq!(advice[0].action, ShopAction::Consider);
+        assert!(advice[0].reason.contains("below 30 gold"));
+        assert_eq!(advice[0].trace.rule, "shop.interest-breakpoint");
+        assert_eq!(advice[1].action, ShopAction::Buy);
+
+        // Recommended buys use up the gold for later slots
+        let advice = shop_advice(
+            &state("2-1", 3, &[("Ahri", 3), ("Poppy", 1)]),
+            Some(&comp()),
+        );
+        assert_eq!(actions(&advice), vec![ShopAction::Buy, ShopAction::Skip]);
+        assert_eq!(advice[1].reason, "Can't afford Poppy");
+    }
+}
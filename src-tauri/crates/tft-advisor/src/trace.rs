@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Why a piece of advice was given: the rule that fired, what it looked at
+/// and what else it weighed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdviceTrace {
+    /// Rule that produced the advice, e.g. "econ.low-hp"
+    pub rule: String,
+    /// State values the rule used, in the order it used them
+    pub inputs: Vec<TraceInput>,
+    /// Threshold comparisons made on the way, including the ones that failed
+    pub checks: Vec<TraceCheck>,
+    /// Other options considered, best first, scored the way the module ranks them
+    pub alternatives: Vec<TraceAlternative>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceInput {
+    pub name: String,
+    pub value: Value,
+}
+
+/// `input op threshold`, e.g. `hp <= 40`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceCheck {
+    pub input: String,
+    pub op: String,
+    pub threshold: Value,
+    pub passed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceAlternative {
+    pub option: String,
+    pub score: f64,
+}
+
+impl AdviceTrace {
+    pub fn new(rule: &str) -> Self {
+        Self {
+            rule: rule.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn input(mut self, name: &str, value: impl Into<Value>) -> Self {
+        self.inputs.push(TraceInput {
+            name: name.to_string(),
+            value: value.into(),
+        });
+        self
+    }
+
+    /// Record a comparison and return its outcome, so it can stand in for
+    /// the condition itself
+    pub fn check(
+        &mut self,
+        input: &str,
+        op: &str,
+        threshold: impl Into<Value>,
+        passed: bool,
+    ) -> bool {
+        self.checks.push(TraceCheck {
+            input: input.to_string(),
+            op: op.to_string(),
+            threshold: threshold.into(),
+            passed,
+        });
+        passed
+    }
+
+    pub fn alternative(&mut self, option: &str, score: f64) {
+        self.alternatives.push(TraceAlternative {
+            option: option.to_string(),
+            score,
+        });
+    }
+
+    /// The same trace attributed to `rule`
+    pub fn fired(&self, rule: &str) -> Self {
+        Self {
+            rule: rule.to_string(),
+            ..self.clone()
+        }
+    }
+
+    /// One line for logs: `econ.low-hp (gold=52, hp=38; hp <= 40)`
+    pub fn summary(&self) -> String {
+        let inputs: Vec<String> = self
+            .inputs
+            .iter()
+            .map(|i| format!("{}={}", i.name, plain(&i.value)))
+            .collect();
+        let checks: Vec<String> = self
+            .checks
+            .iter()
+            .filter(|c| c.passed)
+            .map(|c| format!("{} {} {}", c.input, c.op, plain(&c.threshold)))
+            .collect();
+        match (inputs.is_empty(), checks.is_empty()) {
+            (true, true) => self.rule.clone(),
+            (false, true) => format!("{} ({})", self.rule, inputs.join(", ")),
+            (true, false) => format!("{} ({})", self.rule, checks.join(", ")),
+            (false, false) => format!(
+                "{} ({}; {})",
+                self.rule,
+                inputs.join(", "),
+                checks.join(", ")
+            ),
+        }
+    }
+}
+
+/// Strings without their JSON quotes
+fn plain(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
@@ -67,6 +67,8 @@ pub struct CaptureStatus {
     pub fps: f64,
     pub last_capture_time: Option<u64>,
     pub resolution: Option<(u32, u32)>,
+    /// Presentation time of the latest frame within the source video
+    pub position_ms: Option<u64>,
 }
 
 impl Default for CaptureStatus {
@@ -78,6 +80,7 @@ impl Default for CaptureStatus {
             fps: 0.0,
             last_capture_time: None,
             resolution: None,
+            position_ms: None,
         }
     }
 }
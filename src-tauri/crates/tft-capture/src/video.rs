@@ -25,7 +25,7 @@ pub async fn video_loop(
     info!("Video loop starting: {}", path.display());
 
     let path_owned = path.to_path_buf();
-    let (decode_tx, mut decode_rx) = tokio::sync::mpsc::channel::<RgbaImage>(2);
+    let (decode_tx, mut decode_rx) = tokio::sync::mpsc::channel::<(RgbaImage, Option<u64>)>(2);
 
     // Spawn blocking decode thread
     let stop_decode = stop.clone();
@@ -46,7 +46,7 @@ pub async fn video_loop(
         let tick_start = Instant::now();
 
         match decode_rx.recv().await {
-            Some(frame) => {
+            Some((frame, position_ms)) => {
                 let resolution = (frame.width(), frame.height());
                 frame_count += 1;
 
@@ -74,6 +74,7 @@ pub async fn video_loop(
                     fps,
                     last_capture_time: Some(now),
                     resolution: Some(resolution),
+                    position_ms,
                 });
 
                 let _ = frame_tx.send(Some(Arc::new(frame)));
@@ -105,10 +106,11 @@ pub async fn video_loop(
 }
 
 /// Blocking video decode using ffmpeg-next.
-/// Sends decoded RGBA frames through the mpsc channel.
+/// Sends decoded RGBA frames, with their presentation time in milliseconds,
+/// through the mpsc channel.
 fn decode_video(
     path: &Path,
-    tx: tokio::sync::mpsc::Sender<RgbaImage>,
+    tx: tokio::sync::mpsc::Sender<(RgbaImage, Option<u64>)>,
     stop: Arc<AtomicBool>,
 ) -> anyhow::Result<()> {
     ffmpeg_next::init()?;
@@ -121,6 +123,7 @@ fn decode_video(
         .ok_or_else(|| anyhow::anyhow!("No video stream found"))?;
 
     let stream_index = video_stream.index();
+    let time_base = video_stream.time_base();
     let decoder_ctx = ffmpeg_next::codec::context::Context::from_parameters(video_stream.parameters())?;
     let mut decoder = decoder_ctx.decoder().video()?;
 
@@ -175,8 +178,9 @@ fn decode_video(
                 pixels.extend_from_slice(&data[row_start..row_end]);
             }
 
+            let position_ms = frame_position_ms(&decoded_frame, time_base);
             if let Some(img) = RgbaImage::from_raw(width, height, pixels) {
-                if tx.blocking_send(img).is_err() {
+                if tx.blocking_send((img, position_ms)).is_err() {
                     // Receiver dropped
                     return Ok(());
                 }
@@ -205,8 +209,9 @@ fn decode_video(
             pixels.extend_from_slice(&data[row_start..row_end]);
         }
 
+        let position_ms = frame_position_ms(&decoded_frame, time_base);
         if let Some(img) = RgbaImage::from_raw(width, height, pixels) {
-            if tx.blocking_send(img).is_err() {
+            if tx.blocking_send((img, position_ms)).is_err() {
                 return Ok(());
             }
         }
@@ -214,3 +219,16 @@ fn decode_video(
 
     Ok(())
 }
+
+/// Convert a decoded frame's best-effort timestamp to milliseconds
+fn frame_position_ms(
+    frame: &ffmpeg_next::frame::Video,
+    time_base: ffmpeg_next::Rational,
+) -> Option<u64> {
+    let ts = frame.timestamp()?;
+    if ts < 0 || time_base.denominator() <= 0 {
+        return None;
+    }
+    let ms = ts as i128 * time_base.numerator() as i128 * 1000 / time_base.denominator() as i128;
+    Some(ms as u64)
+}
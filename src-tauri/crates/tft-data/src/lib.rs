@@ -1,3 +1,5 @@
+mod recipes;
+
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -11,6 +13,9 @@ pub struct ChampionData {
     pub cost: u32,
     pub traits: Vec<String>,
     pub icon: String,
+    /// Attack range in hexes, 1 for melee. 0 when champions.json predates it.
+    #[serde(default)]
+    pub range: u32,
 }
 
 /// Item data
@@ -20,6 +25,69 @@ pub struct ItemData {
     pub name: String,
     pub is_component: bool,
     pub recipe: Option<(String, String)>,
+    #[serde(default)]
+    pub icon: String,
+}
+
+/// Trait data with its activation breakpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraitData {
+    pub id: String,
+    pub name: String,
+    /// Unit counts at which the trait activates a new tier, ascending
+    pub breakpoints: Vec<u32>,
+    pub icon: String,
+}
+
+impl TraitData {
+    /// Number of breakpoints reached with `count` units (0 = inactive)
+    pub fn tier_for(&self, count: u32) -> usize {
+        self.breakpoints.iter().take_while(|&&b| count >= b).count()
+    }
+}
+
+/// Augment rarity, as shown by the card frame on the selection screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AugmentTier {
+    Silver,
+    Gold,
+    Prismatic,
+}
+
+/// Augment data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AugmentData {
+    pub id: String,
+    pub name: String,
+    pub tier: AugmentTier,
+    #[serde(default)]
+    pub description: String,
+    pub icon: String,
+}
+
+/// What an augment gives, for fitting it to the game state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AugmentTag {
+    Econ,
+    Items,
+    Level,
+    Health,
+    Combat,
+}
+
+/// An augment's strength from the meta tier list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AugmentRating {
+    pub id: String,
+    /// "S", "A", "B" or "C"
+    pub tier: String,
+    #[serde(default)]
+    pub tags: Vec<AugmentTag>,
+    /// Names of the meta comps the augment is best in
+    #[serde(default)]
+    pub comps: Vec<String>,
 }
 
 /// Meta composition definition
@@ -34,6 +102,38 @@ pub struct MetaComp {
     pub power_spike: String,
 }
 
+/// Copies of each champion in the shared pool, for one cost tier
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolSize {
+    pub cost: u32,
+    pub copies: u32,
+}
+
+/// Pool sizes used when pool.json is missing (Set 16)
+const DEFAULT_POOL_SIZES: [(u32, u32); 5] = [(1, 30), (2, 25), (3, 18), (4, 10), (5, 9)];
+
+/// Shop odds at one player level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelOdds {
+    pub level: u32,
+    /// Percent chance per shop slot of rolling each cost tier, 1-cost first
+    pub chances: Vec<u32>,
+}
+
+/// Shop odds used when shop_odds.json is missing (Set 16)
+const DEFAULT_SHOP_ODDS: [(u32, [u32; 5]); 10] = [
+    (1, [100, 0, 0, 0, 0]),
+    (2, [100, 0, 0, 0, 0]),
+    (3, [75, 25, 0, 0, 0]),
+    (4, [55, 30, 15, 0, 0]),
+    (5, [45, 33, 20, 2, 0]),
+    (6, [30, 40, 25, 5, 0]),
+    (7, [19, 30, 40, 10, 1]),
+    (8, [18, 25, 32, 22, 3]),
+    (9, [15, 20, 25, 30, 10]),
+    (10, [5, 10, 20, 40, 25]),
+];
+
 /// Raw champions.json file format
 #[derive(Debug, Deserialize)]
 struct ChampionsFile {
@@ -44,13 +144,93 @@ struct ChampionsFile {
     champions: Vec<ChampionData>,
 }
 
+/// Raw traits.json file format
+#[derive(Debug, Deserialize)]
+struct TraitsFile {
+    #[allow(dead_code)]
+    version: String,
+    #[allow(dead_code)]
+    set: Option<u32>,
+    traits: Vec<TraitData>,
+}
+
+/// Raw items.json file format
+#[derive(Debug, Deserialize)]
+struct ItemsFile {
+    #[allow(dead_code)]
+    version: String,
+    #[allow(dead_code)]
+    set: Option<u32>,
+    items: Vec<ItemData>,
+}
+
+/// Raw augments.json file format
+#[derive(Debug, Deserialize)]
+struct AugmentsFile {
+    #[allow(dead_code)]
+    version: String,
+    #[allow(dead_code)]
+    set: Option<u32>,
+    augments: Vec<AugmentData>,
+}
+
+/// Raw pool.json file format
+#[derive(Debug, Deserialize)]
+struct PoolFile {
+    #[allow(dead_code)]
+    version: String,
+    #[allow(dead_code)]
+    set: Option<u32>,
+    pool: Vec<PoolSize>,
+}
+
+/// Raw shop_odds.json file format
+#[derive(Debug, Deserialize)]
+struct ShopOddsFile {
+    #[allow(dead_code)]
+    version: String,
+    #[allow(dead_code)]
+    set: Option<u32>,
+    odds: Vec<LevelOdds>,
+}
+
+/// Raw meta/comps.json file format
+#[derive(Debug, Deserialize)]
+struct CompsFile {
+    #[allow(dead_code)]
+    version: String,
+    patch: String,
+    comps: Vec<MetaComp>,
+}
+
+/// Raw meta/augments.json file format
+#[derive(Debug, Deserialize)]
+struct AugmentRatingsFile {
+    #[allow(dead_code)]
+    version: String,
+    patch: String,
+    augments: Vec<AugmentRating>,
+}
+
 /// Game data registry
 #[derive(Debug, Clone, Default)]
 pub struct GameData {
     pub champions: HashMap<String, ChampionData>,
     pub champions_by_name: HashMap<String, String>,
     pub items: HashMap<String, ItemData>,
+    /// Sorted component pair → completed item ID, built from `items` on load
+    pub recipes: HashMap<(String, String), String>,
+    pub traits: HashMap<String, TraitData>,
+    pub traits_by_name: HashMap<String, String>,
+    pub augments: HashMap<String, AugmentData>,
+    pub augments_by_name: HashMap<String, String>,
     pub meta_comps: Vec<MetaComp>,
+    /// Augment ID → tier list rating
+    pub augment_ratings: HashMap<String, AugmentRating>,
+    /// Cost tier → copies of each champion of that cost in the shared pool
+    pub pool_sizes: HashMap<u32, u32>,
+    /// Player level → percent chance per shop slot of each cost tier
+    pub shop_odds: HashMap<u32, Vec<u32>>,
 }
 
 impl GameData {
@@ -80,8 +260,253 @@ impl GameData {
             );
         }
 
+        let traits_path = data_dir.join("traits.json");
+        if traits_path.exists() {
+            let content = std::fs::read_to_string(&traits_path)
+                .context("Failed to read traits.json")?;
+            let file: TraitsFile =
+                serde_json::from_str(&content).context("Failed to parse traits.json")?;
+            if file.traits.is_empty() {
+                anyhow::bail!(
+                    "traits.json at {} lists no traits. Run scripts/fetch-templates.py",
+                    traits_path.display()
+                );
+            }
+
+            for t in file.traits {
+                data.traits_by_name
+                    .insert(t.name.to_lowercase(), t.id.clone());
+                data.traits.insert(t.id.clone(), t);
+            }
+
+            tracing::info!("Loaded {} traits", data.traits.len());
+        } else {
+            tracing::warn!(
+                "No traits.json found at {}. Run scripts/fetch-templates.py",
+                traits_path.display()
+            );
+        }
+
+        if !data.champions.is_empty() && data.champions.values().all(|c| c.traits.is_empty()) {
+            anyhow::bail!(
+                "No champion in champions.json lists its traits. Run scripts/fetch-templates.py"
+            );
+        }
+        if !data.champions.is_empty() && data.champions.values().all(|c| c.range == 0) {
+            anyhow::bail!(
+                "No champion in champions.json has an attack range. Run scripts/fetch-templates.py"
+            );
+        }
+
+        let items_path = data_dir.join("items.json");
+        if items_path.exists() {
+            let content = std::fs::read_to_string(&items_path)
+                .context("Failed to read items.json")?;
+            let file: ItemsFile =
+                serde_json::from_str(&content).context("Failed to parse items.json")?;
+
+            for item in file.items {
+                data.items.insert(item.id.clone(), item);
+            }
+
+            tracing::info!("Loaded {} items", data.items.len());
+        } else {
+            tracing::debug!("No items.json found at {}", items_path.display());
+        }
+        recipes::fill_defaults(&mut data.items);
+        data.recipes = recipes::index(&data.items);
+
+        let augments_path = data_dir.join("augments.json");
+        if augments_path.exists() {
+            let content = std::fs::read_to_string(&augments_path)
+                .context("Failed to read augments.json")?;
+            let file: AugmentsFile =
+                serde_json::from_str(&content).context("Failed to parse augments.json")?;
+
+            for augment in file.augments {
+                data.augments_by_name
+                    .insert(augment.name.to_lowercase(), augment.id.clone());
+                data.augments.insert(augment.id.clone(), augment);
+            }
+
+            tracing::info!("Loaded {} augments", data.augments.len());
+        } else {
+            tracing::debug!("No augments.json found at {}", augments_path.display());
+        }
+
+        let pool_path = data_dir.join("pool.json");
+        if pool_path.exists() {
+            let content =
+                std::fs::read_to_string(&pool_path).context("Failed to read pool.json")?;
+            let file: PoolFile =
+                serde_json::from_str(&content).context("Failed to parse pool.json")?;
+
+            data.pool_sizes = file.pool.into_iter().map(|p| (p.cost, p.copies)).collect();
+            tracing::info!("Loaded pool sizes for {} cost tiers", data.pool_sizes.len());
+        } else {
+            tracing::debug!(
+                "No pool.json found at {}, using default pool sizes",
+                pool_path.display()
+            );
+            data.pool_sizes = DEFAULT_POOL_SIZES.into_iter().collect();
+        }
+
+        let odds_path = data_dir.join("shop_odds.json");
+        if odds_path.exists() {
+            let content =
+                std::fs::read_to_string(&odds_path).context("Failed to read shop_odds.json")?;
+            let file: ShopOddsFile =
+                serde_json::from_str(&content).context("Failed to parse shop_odds.json")?;
+
+            data.shop_odds = file.odds.into_iter().map(|o| (o.level, o.chances)).collect();
+            tracing::info!("Loaded shop odds for {} levels", data.shop_odds.len());
+        } else {
+            tracing::debug!(
+                "No shop_odds.json found at {}, using default shop odds",
+                odds_path.display()
+            );
+            data.shop_odds = DEFAULT_SHOP_ODDS
+                .into_iter()
+                .map(|(level, chances)| (level, chances.to_vec()))
+                .collect();
+        }
+
+        let comps_path = data_dir.join("meta").join("comps.json");
+        if comps_path.exists() {
+            let content = std::fs::read_to_string(&comps_path)
+                .context("Failed to read meta/comps.json")?;
+            let file: CompsFile =
+                serde_json::from_str(&content).context("Failed to parse meta/comps.json")?;
+
+            for comp in file.comps {
+                let unknown = data.unknown_units(&comp);
+                if unknown.is_empty() {
+                    data.meta_comps.push(comp);
+                } else {
+                    tracing::warn!(
+                        "Skipping meta comp {}: unknown unit(s) {}",
+                        comp.name,
+                        unknown.join(", ")
+                    );
+                }
+            }
+
+            tracing::info!(
+                "Loaded {} meta comps for patch {}",
+                data.meta_comps.len(),
+                file.patch
+            );
+        } else {
+            tracing::debug!("No meta/comps.json found at {}", comps_path.display());
+        }
+
+        let ratings_path = data_dir.join("meta").join("augments.json");
+        if ratings_path.exists() {
+            let content = std::fs::read_to_string(&ratings_path)
+                .context("Failed to read meta/augments.json")?;
+            let file: AugmentRatingsFile =
+                serde_json::from_str(&content).context("Failed to parse meta/augments.json")?;
+
+            for rating in file.augments {
+                // Nothing is unknown when no augment data is loaded
+                if !data.augments.is_empty() && !data.augments.contains_key(&rating.id) {
+                    tracing::warn!("Skipping rating for unknown augment {}", rating.id);
+                    continue;
+                }
+                data.augment_ratings.insert(rating.id.clone(), rating);
+            }
+
+            tracing::info!(
+                "Loaded {} augment ratings for patch {}",
+                data.augment_ratings.len(),
+                file.patch
+            );
+        } else {
+            tracing::debug!("No meta/augments.json found at {}", ratings_path.display());
+        }
+
         Ok(data)
     }
+
+    /// Unit IDs in a comp that don't name a known champion. Nothing is unknown
+    /// when no champion data is loaded.
+    pub fn unknown_units<'a>(&self, comp: &'a MetaComp) -> Vec<&'a str> {
+        if self.champions.is_empty() {
+            return Vec::new();
+        }
+        let mut unknown: Vec<&str> = comp
+            .core_units
+            .iter()
+            .chain(&comp.flex_units)
+            .chain(&comp.early_game)
+            .chain(comp.core_items.keys())
+            .map(String::as_str)
+            .filter(|id| !self.champions.contains_key(*id))
+            .collect();
+        unknown.sort_unstable();
+        unknown.dedup();
+        unknown
+    }
+
+    /// Chance (0-1) that a shop slot rolls a champion of `cost` at `level`
+    pub fn shop_chance(&self, level: u32, cost: u32) -> Option<f64> {
+        let chances = self.shop_odds.get(&level)?;
+        let percent = chances.get(cost.checked_sub(1)? as usize)?;
+        Some(*percent as f64 / 100.0)
+    }
+
+    /// The completed item two components build, in either order
+    pub fn combine(&self, a: &str, b: &str) -> Option<&ItemData> {
+        self.recipes
+            .get(&recipes::pair(a, b))
+            .and_then(|id| self.items.get(id))
+    }
+
+    /// Completed item ID → its two components, for every item with a recipe
+    pub fn item_recipes(&self) -> HashMap<String, (String, String)> {
+        self.items
+            .values()
+            .filter_map(|item| Some((item.id.clone(), item.recipe.clone()?)))
+            .collect()
+    }
+
+    /// Copies of a champion of the given cost in the shared pool
+    pub fn pool_size(&self, cost: u32) -> Option<u32> {
+        self.pool_sizes.get(&cost).copied()
+    }
+
+    /// Total copies in the shared pool for every known champion
+    pub fn champion_pool(&self) -> HashMap<String, u32> {
+        self.champions
+            .values()
+            .filter_map(|c| Some((c.id.clone(), self.pool_size(c.cost)?)))
+            .collect()
+    }
+
+    /// Count units per trait for a set of champion IDs. Duplicate copies of the
+    /// same champion only count once, as in game.
+    pub fn trait_counts(&self, champion_ids: &[&str]) -> HashMap<String, u32> {
+        let mut seen = std::collections::HashSet::new();
+        let mut counts = HashMap::new();
+        for id in champion_ids {
+            if !seen.insert(*id) {
+                continue;
+            }
+            if let Some(champ) = self.champions.get(*id) {
+                for t in &champ.traits {
+                    *counts.entry(t.clone()).or_insert(0) += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Look up an augment by its display name, ignoring case and surrounding whitespace
+    pub fn augment_by_name(&self, name: &str) -> Option<&AugmentData> {
+        self.augments_by_name
+            .get(&name.trim().to_lowercase())
+            .and_then(|id| self.augments.get(id))
+    }
 }
 
 #[cfg(test)]
@@ -93,4 +518,269 @@ mod tests {
         let data = GameData::load(Path::new("/nonexistent")).unwrap();
         assert!(data.champions.is_empty());
     }
+
+    #[test]
+    fn test_load_shipped_data() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../../data");
+        let data = GameData::load(&dir).unwrap();
+        assert_eq!(data.champions.len(), 100);
+        assert!(data.champions.values().all(|c| !c.traits.is_empty() && c.range > 0));
+        for champ in data.champions.values() {
+            for t in &champ.traits {
+                assert!(data.traits.contains_key(t), "{} has unknown trait {}", champ.id, t);
+            }
+        }
+        assert_eq!(data.traits_by_name["shadow isles"], "TFT16_ShadowIsles");
+        assert!(!data.meta_comps.is_empty());
+        for comp in &data.meta_comps {
+            assert!(data.unknown_units(comp).is_empty(), "{} names unknown units", comp.name);
+        }
+    }
+
+    #[test]
+    fn test_trait_tiers_and_counts() {
+        let mut data = GameData::default();
+        for (id, traits) in [("A", vec!["Bruiser"]), ("B", vec!["Bruiser", "Mage"])] {
+            data.champions.insert(
+                id.to_string(),
+                ChampionData {
+                    id: id.to_string(),
+                    name: id.to_string(),
+                    cost: 1,
+                    traits: traits.into_iter().map(String::from).collect(),
+                    icon: String::new(),
+                    range: 1,
+                },
+            );
+        }
+        let counts = data.trait_counts(&["A", "B", "A"]);
+        assert_eq!(counts.get("Bruiser"), Some(&2));
+        assert_eq!(counts.get("Mage"), Some(&1));
+
+        let bruiser = TraitData {
+            id: "Bruiser".to_string(),
+            name: "Bruiser".to_string(),
+            breakpoints: vec![2, 4, 6],
+            icon: String::new(),
+        };
+        assert_eq!(bruiser.tier_for(1), 0);
+        assert_eq!(bruiser.tier_for(5), 2);
+        assert_eq!(bruiser.tier_for(9), 3);
+    }
+
+    #[test]
+    fn test_placeholder_traits_fail() {
+        let dir = std::env::temp_dir().join(format!("tft_data_test_traits_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("traits.json"),
+            r#"{"version": "test", "set": 16, "traits": []}"#,
+        )
+        .unwrap();
+        let empty = GameData::load(&dir);
+
+        std::fs::write(
+            dir.join("traits.json"),
+            r#"{"version": "test", "set": 16, "traits": [
+                {"id": "TFT16_Bruiser", "name": "Bruiser", "breakpoints": [2, 4], "icon": ""}
+            ]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("champions.json"),
+            r#"{"version": "test", "set": 16, "champions": [
+                {"id": "TFT16_Vi", "name": "Vi", "cost": 2, "traits": [], "icon": ""}
+            ]}"#,
+        )
+        .unwrap();
+        let untraited = GameData::load(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(format!("{:#}", empty.unwrap_err()).contains("lists no traits"));
+        assert!(format!("{:#}", untraited.unwrap_err()).contains("No champion"));
+    }
+
+    #[test]
+    fn test_load_augments() {
+        let dir =
+            std::env::temp_dir().join(format!("tft_data_test_augments_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let missing = GameData::load(&dir).unwrap();
+        std::fs::write(
+            dir.join("augments.json"),
+            r#"{"version": "test", "set": 16, "augments": [
+                {"id": "TFT_Augment_A", "name": "Pandora's Items", "tier": "gold", "icon": "a.png"}
+            ]}"#,
+        )
+        .unwrap();
+
+        std::fs::create_dir_all(dir.join("meta")).unwrap();
+        std::fs::write(
+            dir.join("meta").join("augments.json"),
+            r#"{"version": "test", "patch": "16.4", "augments": [
+                {"id": "TFT_Augment_A", "tier": "S", "tags": ["items"]},
+                {"id": "TFT_Augment_Unknown", "tier": "A"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let data = GameData::load(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(missing.augments.is_empty());
+        let augment = data.augment_by_name(" pandora's items").unwrap();
+        assert_eq!(augment.id, "TFT_Augment_A");
+        assert_eq!(augment.tier, AugmentTier::Gold);
+        // Ratings for augments missing from augments.json are dropped
+        assert_eq!(data.augment_ratings.len(), 1);
+        let rating = &data.augment_ratings["TFT_Augment_A"];
+        assert_eq!(rating.tier, "S");
+        assert_eq!(rating.tags, vec![AugmentTag::Items]);
+        assert!(rating.comps.is_empty());
+    }
+
+    #[test]
+    fn test_load_items() {
+        let dir = std::env::temp_dir().join(format!("tft_data_test_items_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let missing = GameData::load(&dir).unwrap();
+        std::fs::write(
+            dir.join("items.json"),
+            r#"{"version": "test", "set": 16, "items": [
+                {"id": "TFT_Item_BFSword", "name": "B.F. Sword", "is_component": true, "recipe": null, "icon": "bf.png"},
+                {"id": "TFT_Item_Deathblade", "name": "Deathblade", "is_component": false,
+                 "recipe": ["TFT_Item_BFSword", "TFT_Item_BFSword"]},
+                {"id": "TFT16_Item_TestEmblemItem", "name": "Test Emblem", "is_component": false,
+                 "recipe": ["TFT_Item_Spatula", "TFT_Item_BFSword"]}
+            ]}"#,
+        )
+        .unwrap();
+
+        let data = GameData::load(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // The standard table is built in
+        let deathblade = missing.combine("TFT_Item_BFSword", "TFT_Item_BFSword").unwrap();
+        assert_eq!(deathblade.id, "TFT_Item_Deathblade");
+        // File entries win over the standard table
+        assert_eq!(data.items["TFT_Item_BFSword"].icon, "bf.png");
+        assert!(data.items["TFT_Item_BFSword"].is_component);
+        let deathblade = &data.items["TFT_Item_Deathblade"];
+        assert_eq!(
+            deathblade.recipe,
+            Some(("TFT_Item_BFSword".to_string(), "TFT_Item_BFSword".to_string()))
+        );
+        assert!(deathblade.icon.is_empty());
+
+        // Standard recipes fill in what the file leaves out
+        let bloodthirster = data.combine("TFT_Item_NegatronCloak", "TFT_Item_BFSword").unwrap();
+        assert_eq!(bloodthirster.id, "TFT_Item_Bloodthirster");
+        assert!(data.items["TFT_Item_SparringGloves"].is_component);
+        assert_eq!(
+            data.combine("TFT_Item_FryingPan", "TFT_Item_Spatula").unwrap().name,
+            "Tactician's Cape"
+        );
+        // Emblems are set-specific and only come from the loaded file
+        let emblem = data.combine("TFT_Item_BFSword", "TFT_Item_Spatula").unwrap();
+        assert_eq!(emblem.id, "TFT16_Item_TestEmblemItem");
+    }
+
+    #[test]
+    fn test_load_comps() {
+        let dir = std::env::temp_dir().join(format!("tft_data_test_comps_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("meta")).unwrap();
+        std::fs::write(
+            dir.join("champions.json"),
+            r#"{"version": "test", "set": 16, "champions": [
+                {"id": "TFT16_Jinx", "name": "Jinx", "cost": 2, "traits": [], "icon": ""},
+                {"id": "TFT16_Vi", "name": "Vi", "cost": 2, "traits": ["TFT16_Brawler"], "icon": "",
+                 "range": 1},
+                {"id": "TFT16_Lux", "name": "Lux", "cost": 3, "traits": ["TFT16_Sorcerer"], "icon": ""}
+            ]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("meta").join("comps.json"),
+            r#"{"version": "test", "patch": "16.4", "comps": [
+                {"name": "Jinx Reroll", "tier": "S", "core_units": ["TFT16_Jinx"],
+                 "flex_units": ["TFT16_Vi"], "core_items": {"TFT16_Jinx": ["TFT_Item_Deathblade"]},
+                 "early_game": [], "power_spike": "3-2"},
+                {"name": "Typo", "tier": "B", "core_units": ["TFT16_Jnx"], "flex_units": [],
+                 "core_items": {"TFT16_Zed": []}, "early_game": [], "power_spike": "4-1"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let data = GameData::load(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(data.meta_comps.len(), 1);
+        assert_eq!(data.meta_comps[0].name, "Jinx Reroll");
+
+        let typo = MetaComp {
+            core_units: vec!["TFT16_Jnx".to_string(), "TFT16_Jinx".to_string()],
+            ..data.meta_comps[0].clone()
+        };
+        assert_eq!(data.unknown_units(&typo), vec!["TFT16_Jnx"]);
+
+    }
+
+    #[test]
+    fn test_load_pool() {
+        let dir = std::env::temp_dir().join(format!("tft_data_test_pool_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let missing = GameData::load(&dir).unwrap();
+        std::fs::write(
+            dir.join("champions.json"),
+            r#"{"version": "test", "set": 16, "champions": [
+                {"id": "TFT16_Ahri", "name": "Ahri", "cost": 3, "traits": ["TFT16_Ionia"],
+                 "icon": "", "range": 4},
+                {"id": "TFT16_Odd", "name": "Odd", "cost": 7, "traits": [], "icon": ""}
+            ]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("pool.json"),
+            r#"{"version": "test", "set": 16, "pool": [
+                {"cost": 1, "copies": 29}, {"cost": 3, "copies": 17}
+            ]}"#,
+        )
+        .unwrap();
+
+        let data = GameData::load(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // Set 16 sizes without a pool.json
+        assert_eq!(missing.pool_size(1), Some(30));
+        assert_eq!(missing.pool_size(5), Some(9));
+        assert_eq!(data.pool_size(1), Some(29));
+        assert_eq!(data.pool_size(2), None);
+        // Champions with a cost outside the pool table are not tracked
+        let pool = data.champion_pool();
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool["TFT16_Ahri"], 17);
+    }
+
+    #[test]
+    fn test_load_shop_odds() {
+        let dir = std::env::temp_dir().join(format!("tft_data_test_odds_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let missing = GameData::load(&dir).unwrap();
+        std::fs::write(
+            dir.join("shop_odds.json"),
+            r#"{"version": "test", "set": 16, "odds": [
+                {"level": 7, "chances": [20, 30, 35, 14, 1]}
+            ]}"#,
+        )
+        .unwrap();
+        let data = GameData::load(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // Set 16 odds without a shop_odds.json
+        assert_eq!(missing.shop_chance(7, 3), Some(0.4));
+        assert_eq!(missing.shop_chance(11, 1), None);
+        assert_eq!(data.shop_chance(7, 3), Some(0.35));
+        assert_eq!(data.shop_chance(7, 0), None);
+        assert_eq!(data.shop_chance(8, 1), None);
+    }
 }
@@ -0,0 +1,53 @@
+use crate::{ItemData, ItemsFile};
+use std::collections::HashMap;
+
+/// Standard components, completed items and their recipes, built in so item
+/// advice works without a data directory
+const SHIPPED_ITEMS: &str = include_str!("../data/items.json");
+
+/// Add the shipped components and recipes for anything the loaded items.json
+/// left out, and fill in recipes it listed without one.
+pub(crate) fn fill_defaults(items: &mut HashMap<String, ItemData>) {
+    let shipped: ItemsFile = match serde_json::from_str(SHIPPED_ITEMS) {
+        Ok(file) => file,
+        Err(e) => {
+            tracing::warn!("Built-in items.json is invalid: {}", e);
+            return;
+        }
+    };
+
+    for default in shipped.items {
+        let item = items
+            .entry(default.id.clone())
+            .or_insert_with(|| ItemData {
+                recipe: None,
+                ..default.clone()
+            });
+        if item.recipe.is_none() && !item.is_component {
+            item.recipe = default.recipe;
+        }
+    }
+}
+
+/// Component pair, in sorted order → ID of the item the pair combines into.
+/// When two items share a recipe the lowest ID wins.
+pub(crate) fn index(items: &HashMap<String, ItemData>) -> HashMap<(String, String), String> {
+    let mut ids: Vec<&String> = items.keys().collect();
+    ids.sort();
+    let mut recipes = HashMap::new();
+    for id in ids {
+        if let Some((a, b)) = &items[id].recipe {
+            recipes.entry(pair(a, b)).or_insert_with(|| id.clone());
+        }
+    }
+    recipes
+}
+
+/// Order-independent key for a component pair
+pub(crate) fn pair(a: &str, b: &str) -> (String, String) {
+    if a <= b {
+        (a.to_string(), b.to_string())
+    } else {
+        (b.to_string(), a.to_string())
+    }
+}
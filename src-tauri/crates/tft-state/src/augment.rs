@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+
+/// Consecutive frames without the overlay before a pick is committed, so a
+/// single missed detection does not end the selection early
+const CLOSE_AFTER_FRAMES: u32 = 2;
+
+/// One augment card as seen on a single frame of the selection screen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfferedAugment {
+    pub slot: usize,
+    pub augment_id: Option<String>,
+    pub name: Option<String>,
+    pub highlighted: bool,
+}
+
+/// Works out which augment the player picked by watching the selection overlay.
+///
+/// While the overlay is up we remember the latest recognised augment in each slot
+/// (rerolls replace them) and which card was last highlighted. When the overlay
+/// closes, the pick is the last card left standing if the others faded out first,
+/// otherwise the last highlighted card.
+#[derive(Debug, Clone, Default)]
+pub struct AugmentTracker {
+    offer: Vec<Option<String>>,
+    last_highlight: Option<usize>,
+    last_visible: Vec<usize>,
+    absent_frames: u32,
+    chosen: Vec<String>,
+}
+
+impl AugmentTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the augment cards seen on one frame (empty when the overlay is not
+    /// showing). Returns the picked augment ID when a selection completes.
+    pub fn observe(&mut self, cards: &[OfferedAugment]) -> Option<&str> {
+        if !cards.is_empty() {
+            self.absent_frames = 0;
+            for card in cards {
+                if self.offer.len() <= card.slot {
+                    self.offer.resize(card.slot + 1, None);
+                }
+                if card.augment_id.is_some() {
+                    self.offer[card.slot] = card.augment_id.clone();
+                }
+                if card.highlighted {
+                    self.last_highlight = Some(card.slot);
+                }
+            }
+            self.last_visible = cards.iter().map(|c| c.slot).collect();
+            return None;
+        }
+
+        if self.offer.is_empty() {
+            return None;
+        }
+
+        self.absent_frames += 1;
+        if self.absent_frames < CLOSE_AFTER_FRAMES {
+            return None;
+        }
+
+        let picked_slot = if self.last_visible.len() == 1 && self.offer.len() > 1 {
+            Some(self.last_visible[0])
+        } else {
+            self.last_highlight
+        };
+        let picked = picked_slot.and_then(|slot| self.offer.get(slot).cloned().flatten());
+
+        self.offer.clear();
+        self.last_highlight = None;
+        self.last_visible.clear();
+        self.absent_frames = 0;
+
+        match picked {
+            Some(id) => {
+                tracing::info!("Augment picked: {}", id);
+                self.chosen.push(id);
+                self.chosen.last().map(|s| s.as_str())
+            }
+            None => {
+                tracing::debug!("Augment overlay closed without a recognisable pick");
+                None
+            }
+        }
+    }
+
+    /// Augment IDs picked so far, in order
+    pub fn chosen(&self) -> &[String] {
+        &self.chosen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(slot: usize, id: &str, highlighted: bool) -> OfferedAugment {
+        OfferedAugment {
+            slot,
+            augment_id: Some(id.to_string()),
+            name: None,
+            highlighted,
+        }
+    }
+
+    #[test]
+    fn test_pick_follows_last_highlight() {
+        let mut tracker = AugmentTracker::new();
+        tracker.observe(&[card(0, "A", false), card(1, "B", true), card(2, "C", false)]);
+        tracker.observe(&[card(0, "A", true), card(1, "B", false), card(2, "C", false)]);
+        // One missed frame must not commit a pick
+        assert!(tracker.observe(&[]).is_none());
+        assert_eq!(tracker.observe(&[]), Some("A"));
+        assert_eq!(tracker.chosen(), &["A".to_string()]);
+    }
+
+    #[test]
+    fn test_pick_is_last_card_standing() {
+        let mut tracker = AugmentTracker::new();
+        tracker.observe(&[card(0, "A", false), card(1, "B", false), card(2, "C", false)]);
+        tracker.observe(&[card(2, "C", false)]);
+        tracker.observe(&[]);
+        assert_eq!(tracker.observe(&[]), Some("C"));
+    }
+}
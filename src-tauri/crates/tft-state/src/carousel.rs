@@ -0,0 +1,201 @@
+use serde::{Deserialize, Serialize};
+
+/// HUD frames to wait after a carousel for the picked unit to show up on the
+/// bench before giving up
+const PICK_WAIT_FRAMES: u32 = 10;
+
+/// A unit seen on the carousel ring
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CarouselOffer {
+    pub champion_id: String,
+    pub item_id: Option<String>,
+}
+
+/// The unit the local player took from a carousel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CarouselPick {
+    pub stage: Option<String>,
+    pub champion_id: String,
+    /// Item the unit carried, when it could be told apart from other copies
+    pub item_id: Option<String>,
+    pub timestamp_ms: u64,
+}
+
+/// Works out the carousel pick by comparing the bench before and after the
+/// carousel: the pick is the unit that newly appears on the bench and was
+/// standing on the ring.
+#[derive(Debug, Clone, Default)]
+pub struct CarouselTracker {
+    in_carousel: bool,
+    stage: Option<String>,
+    offers: Vec<CarouselOffer>,
+    bench_before: Vec<String>,
+    /// HUD frames seen since the carousel ended, while waiting for the pick
+    waiting: Option<u32>,
+    picks: Vec<CarouselPick>,
+}
+
+impl CarouselTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one frame: whether it shows the carousel, the units on the ring and
+    /// the champion IDs on the bench. Returns the pick once it is identified.
+    pub fn observe(
+        &mut self,
+        carousel: bool,
+        stage: Option<&str>,
+        offered: &[CarouselOffer],
+        bench: &[String],
+        timestamp_ms: u64,
+    ) -> Option<&CarouselPick> {
+        if carousel {
+            if !self.in_carousel {
+                self.in_carousel = true;
+                self.waiting = None;
+                self.stage = stage.map(String::from);
+                self.offers.clear();
+            }
+            if self.stage.is_none() {
+                self.stage = stage.map(String::from);
+            }
+            for offer in offered {
+                if !self.offers.contains(offer) {
+                    self.offers.push(offer.clone());
+                }
+            }
+            return None;
+        }
+
+        if self.in_carousel {
+            self.in_carousel = false;
+            if self.offers.is_empty() {
+                tracing::debug!("Carousel ended without any recognised units");
+            } else {
+                self.waiting = Some(0);
+            }
+        }
+
+        let Some(frames) = self.waiting else {
+            if !bench.is_empty() {
+                self.bench_before = bench.to_vec();
+            }
+            return None;
+        };
+
+        if bench.is_empty() {
+            return None;
+        }
+
+        match self.find_pick(bench) {
+            Some(champion_id) => {
+                let mut items = self
+                    .offers
+                    .iter()
+                    .filter(|o| o.champion_id == champion_id)
+                    .map(|o| o.item_id.clone());
+                let item_id = match (items.next(), items.next()) {
+                    (Some(item), None) => item,
+                    _ => None,
+                };
+                tracing::info!("Carousel pick: {} ({:?})", champion_id, item_id);
+                self.picks.push(CarouselPick {
+                    stage: self.stage.take(),
+                    champion_id,
+                    item_id,
+                    timestamp_ms,
+                });
+                self.waiting = None;
+                self.bench_before = bench.to_vec();
+                self.picks.last()
+            }
+            None if frames + 1 >= PICK_WAIT_FRAMES => {
+                tracing::debug!("Carousel pick not found on the bench");
+                self.waiting = None;
+                self.bench_before = bench.to_vec();
+                None
+            }
+            None => {
+                self.waiting = Some(frames + 1);
+                None
+            }
+        }
+    }
+
+    /// Carousel picks so far, in order
+    pub fn picks(&self) -> &[CarouselPick] {
+        &self.picks
+    }
+
+    /// First bench champion with more copies than before the carousel that was
+    /// also on the ring
+    fn find_pick(&self, bench: &[String]) -> Option<String> {
+        let count = |list: &[String], id: &str| list.iter().filter(|b| *b == id).count();
+        bench
+            .iter()
+            .find(|id| {
+                count(bench, id) > count(&self.bench_before, id)
+                    && self.offers.iter().any(|o| &o.champion_id == *id)
+            })
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offer(id: &str, item: &str) -> CarouselOffer {
+        CarouselOffer {
+            champion_id: id.to_string(),
+            item_id: Some(item.to_string()),
+        }
+    }
+
+    fn ids(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_pick_is_new_bench_unit_from_ring() {
+        let mut tracker = CarouselTracker::new();
+        tracker.observe(false, Some("2-3"), &[], &ids(&["Jinx", "Vi"]), 0);
+        tracker.observe(
+            true,
+            Some("2-4"),
+            &[offer("Vi", "Tear"), offer("Ahri", "Bow")],
+            &[],
+            1,
+        );
+        tracker.observe(true, Some("2-4"), &[offer("Jinx", "Belt")], &[], 2);
+        // Bench not read yet on the first HUD frame
+        assert!(tracker.observe(false, Some("2-5"), &[], &[], 3).is_none());
+        let pick = tracker
+            .observe(false, Some("2-5"), &[], &ids(&["Jinx", "Vi", "Vi"]), 4)
+            .unwrap();
+        assert_eq!(pick.champion_id, "Vi");
+        assert_eq!(pick.item_id.as_deref(), Some("Tear"));
+        assert_eq!(pick.stage.as_deref(), Some("2-4"));
+        assert_eq!(tracker.picks().len(), 1);
+    }
+
+    #[test]
+    fn test_gives_up_without_bench_change() {
+        let mut tracker = CarouselTracker::new();
+        tracker.observe(false, Some("3-3"), &[], &ids(&["Jinx"]), 0);
+        tracker.observe(true, Some("3-4"), &[offer("Ahri", "Bow")], &[], 1);
+        for ts in 2..2 + PICK_WAIT_FRAMES as u64 {
+            assert!(tracker
+                .observe(false, Some("3-5"), &[], &ids(&["Jinx"]), ts)
+                .is_none());
+        }
+        assert!(tracker.picks().is_empty());
+        // The next carousel starts from a clean slate
+        tracker.observe(true, Some("4-4"), &[offer("Ahri", "Bow")], &[], 50);
+        let pick = tracker.observe(false, Some("4-5"), &[], &ids(&["Jinx", "Ahri"]), 51);
+        assert_eq!(pick.unwrap().champion_id, "Ahri");
+    }
+}
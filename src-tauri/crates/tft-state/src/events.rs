@@ -0,0 +1,525 @@
+use crate::{parse_stage, GameState, RoundOutcome};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Shop slots that must change champion at once to count as a reroll
+const REROLL_MIN_CHANGED: usize = 3;
+
+/// Gold cost of buying XP
+const XP_COST: u32 = 4;
+
+/// Interest is 1 gold per 10 banked, capped at 5
+const MAX_INTEREST: u32 = 5;
+
+/// Something that happened between two consecutive game states
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum GameEventKind {
+    ChampionBought { champion_id: String, cost: u32 },
+    ChampionSold { champion_id: String },
+    ShopRerolled,
+    XpPurchased,
+    LevelUp { level: u32 },
+    StarUp { champion_id: String, star_level: u32 },
+    /// `round` is the stage that started
+    RoundStarted { round: String },
+    /// `round` is the stage that ended
+    RoundEnded { round: String, outcome: Option<RoundOutcome> },
+    ItemCombined { item_id: String, components: Vec<String> },
+    InterestGained { amount: u32 },
+}
+
+/// A game event positioned in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameEvent {
+    pub timestamp_ms: u64,
+    /// Stage the event happened in
+    pub stage: String,
+    #[serde(flatten)]
+    pub kind: GameEventKind,
+}
+
+/// Derives semantic events by diffing each game state against the previous one.
+#[derive(Debug, Clone, Default)]
+pub struct EventDetector {
+    prev: Option<GameState>,
+    /// Completed item ID → the two components it is built from
+    recipes: HashMap<String, (String, String)>,
+}
+
+impl EventDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `recipes` maps each completed item ID to its two components, so
+    /// combines can name what they consumed
+    pub fn with_recipes(recipes: HashMap<String, (String, String)>) -> Self {
+        Self {
+            recipes,
+            ..Default::default()
+        }
+    }
+
+    /// Compare a new state with the previous one and return what happened in between.
+    pub fn observe(&mut self, state: &GameState, timestamp_ms: u64) -> Vec<GameEvent> {
+        let Some(prev) = self.prev.replace(state.clone()) else {
+            return Vec::new();
+        };
+
+        let kinds = diff(&prev, state, &self.recipes);
+        kinds
+            .into_iter()
+            .map(|kind| GameEvent {
+                timestamp_ms,
+                stage: state.stage.clone(),
+                kind,
+            })
+            .collect()
+    }
+}
+
+fn diff(
+    prev: &GameState,
+    cur: &GameState,
+    recipes: &HashMap<String, (String, String)>,
+) -> Vec<GameEventKind> {
+    let mut events = Vec::new();
+
+    let advanced = match (parse_stage(&prev.stage), parse_stage(&cur.stage)) {
+        (Some(a), Some(b)) => b > a,
+        (None, Some(_)) => true,
+        _ => false,
+    };
+    if advanced {
+        if !prev.stage.is_empty() {
+            let outcome = cur
+                .rounds
+                .iter()
+                .rev()
+                .find(|r| r.stage == prev.stage)
+                .map(|r| r.outcome);
+            events.push(GameEventKind::RoundEnded {
+                round: prev.stage.clone(),
+                outcome,
+            });
+        }
+        events.push(GameEventKind::RoundStarted {
+            round: cur.stage.clone(),
+        });
+        let interest = (prev.gold / 10).min(MAX_INTEREST);
+        if interest > 0 && !prev.stage.is_empty() {
+            events.push(GameEventKind::InterestGained { amount: interest });
+        }
+    }
+
+    if cur.level > prev.level && prev.level > 0 {
+        events.push(GameEventKind::LevelUp { level: cur.level });
+    }
+
+    // Shop changes only mean something within a round, with both shops read
+    let shops_read = !prev.shop.is_empty() && !cur.shop.is_empty();
+    let mut rerolled = false;
+    let mut bought_cost = 0;
+    if !advanced && shops_read {
+        let changed = prev
+            .shop
+            .iter()
+            .zip(&cur.shop)
+            // A refilled or replaced slot; emptied slots are purchases
+            .filter(|(a, b)| match (&a.champion, &b.champion) {
+                (Some(a), Some(b)) => a.id != b.id,
+                (None, Some(_)) => true,
+                _ => false,
+            })
+            .count();
+        rerolled = changed >= REROLL_MIN_CHANGED;
+        if rerolled {
+            events.push(GameEventKind::ShopRerolled);
+        } else {
+            // A slot only empties when its champion is bought
+            for (a, b) in prev.shop.iter().zip(&cur.shop) {
+                if let (Some(champion), None) = (&a.champion, &b.champion) {
+                    bought_cost += champion.cost;
+                    events.push(GameEventKind::ChampionBought {
+                        champion_id: champion.id.clone(),
+                        cost: champion.cost,
+                    });
+                }
+            }
+        }
+    }
+
+    // Gold spent on neither units nor rerolls went into XP
+    if !advanced && !rerolled && cur.phase.has_hud() && prev.phase.has_hud() {
+        let spent = prev.gold.saturating_sub(cur.gold);
+        if spent == bought_cost + XP_COST {
+            events.push(GameEventKind::XpPurchased);
+        }
+    }
+
+    events.extend(unit_events(prev, cur));
+    events.extend(item_events(prev, cur, recipes));
+    events
+}
+
+/// Star-ups and sales, from per-champion copy counts on bench and board
+fn unit_events(prev: &GameState, cur: &GameState) -> Vec<GameEventKind> {
+    let before = unit_counts(prev);
+    let after = unit_counts(cur);
+    let mut events = Vec::new();
+
+    for (id, stars_before) in &before {
+        let stars_after = after.get(id).cloned().unwrap_or_default();
+        let best_before = stars_before.keys().max().copied().unwrap_or(0);
+        let best_after = stars_after.keys().max().copied().unwrap_or(0);
+        if best_after > best_before {
+            events.push(GameEventKind::StarUp {
+                champion_id: id.clone(),
+                star_level: best_after,
+            });
+            continue;
+        }
+
+        // One-star equivalents: a 2-star is three copies, a 3-star nine
+        let copies = |stars: &HashMap<u32, u32>| -> u32 {
+            stars.iter().map(|(s, n)| 3u32.pow(s.saturating_sub(1)) * n).sum()
+        };
+        if copies(&stars_after) < copies(stars_before) && cur.gold > prev.gold {
+            events.push(GameEventKind::ChampionSold {
+                champion_id: id.clone(),
+            });
+        }
+    }
+    events
+}
+
+/// champion ID → star level → number of units
+fn unit_counts(state: &GameState) -> HashMap<String, HashMap<u32, u32>> {
+    let mut counts: HashMap<String, HashMap<u32, u32>> = HashMap::new();
+    for slot in state.bench.iter().chain(&state.board) {
+        if let Some(champion) = &slot.champion {
+            *counts
+                .entry(champion.id.clone())
+                .or_default()
+                .entry(slot.star_level.max(1))
+                .or_default() += 1;
+        }
+    }
+    counts
+}
+
+/// Completed items that appeared while two components disappeared. Each new
+/// item claims its recipe's components from the lost ones; an item whose
+/// recipe is unknown or whose components were not both lost is reported
+/// without components, as long as two unclaimed components went missing.
+/// Events are ordered by item ID.
+fn item_events(
+    prev: &GameState,
+    cur: &GameState,
+    recipes: &HashMap<String, (String, String)>,
+) -> Vec<GameEventKind> {
+    let before = item_counts(prev);
+    let after = item_counts(cur);
+
+    // Component ID → copies that disappeared
+    let mut lost: HashMap<&str, u32> = HashMap::new();
+    for ((id, is_component), n) in &before {
+        let now = after.get(&(id.clone(), *is_component)).copied().unwrap_or(0);
+        if *is_component && now < *n {
+            lost.insert(id, n - now);
+        }
+    }
+
+    let mut new_items: Vec<&str> = Vec::new();
+    for ((id, is_component), n) in &after {
+        let was = before.get(&(id.clone(), *is_component)).copied().unwrap_or(0);
+        if !*is_component && *n > was {
+            new_items.extend(std::iter::repeat_n(id.as_str(), (n - was) as usize));
+        }
+    }
+    new_items.sort();
+
+    let copies = |lost: &HashMap<&str, u32>, c: &str| lost.get(c).copied().unwrap_or(0);
+    // (item ID, components) per combine
+    let mut combines: Vec<(String, Vec<String>)> = Vec::new();
+    let mut unresolved = Vec::new();
+    for id in new_items {
+        let claimed = recipes.get(id).filter(|(a, b)| {
+            if a == b {
+                copies(&lost, a) >= 2
+            } else {
+                copies(&lost, a) >= 1 && copies(&lost, b) >= 1
+            }
+        });
+        let Some((a, b)) = claimed else {
+            unresolved.push(id);
+            continue;
+        };
+        for c in [a, b] {
+            if let Some(n) = lost.get_mut(c.as_str()) {
+                *n -= 1;
+            }
+        }
+        let mut components = vec![a.clone(), b.clone()];
+        components.sort();
+        combines.push((id.to_string(), components));
+    }
+
+    let mut unclaimed: u32 = lost.values().sum();
+    for id in unresolved {
+        if unclaimed < 2 {
+            break;
+        }
+        unclaimed -= 2;
+        combines.push((id.to_string(), Vec::new()));
+    }
+
+    combines.sort();
+    combines
+        .into_iter()
+        .map(|(item_id, components)| GameEventKind::ItemCombined {
+            item_id,
+            components,
+        })
+        .collect()
+}
+
+/// (item ID, is component) → count over the item bench and all units
+fn item_counts(state: &GameState) -> HashMap<(String, bool), u32> {
+    let mut counts = HashMap::new();
+    let held = state
+        .bench
+        .iter()
+        .chain(&state.board)
+        .flat_map(|slot| slot.items.iter());
+    for item in state.items.iter().chain(held) {
+        *counts
+            .entry((item.id.clone(), item.is_component))
+            .or_insert(0) += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoardSlot, Champion, GamePhase, Item, ShopSlot};
+
+    fn champion(id: &str, cost: u32) -> Champion {
+        Champion {
+            id: id.to_string(),
+            name: id.to_string(),
+            cost,
+            traits: Vec::new(),
+        }
+    }
+
+    fn shop(ids: &[Option<&str>]) -> Vec<ShopSlot> {
+        ids.iter()
+            .enumerate()
+            .map(|(index, id)| ShopSlot {
+                index,
+                champion: id.map(|id| champion(id, 2)),
+                confidence: 0.9,
+            })
+            .collect()
+    }
+
+    fn state(stage: &str, gold: u32, level: u32, ids: &[Option<&str>]) -> GameState {
+        GameState {
+            phase: GamePhase::Planning,
+            stage: stage.to_string(),
+            gold,
+            level,
+            shop: shop(ids),
+            ..Default::default()
+        }
+    }
+
+    fn kinds(detector: &mut EventDetector, state: &GameState) -> Vec<GameEventKind> {
+        detector.observe(state, 0).into_iter().map(|e| e.kind).collect()
+    }
+
+    #[test]
+    fn test_buy_reroll_xp_and_round_events() {
+        let mut detector = EventDetector::new();
+        let full = [Some("A"), Some("B"), Some("C"), Some("D"), Some("E")];
+        assert!(kinds(&mut detector, &state("3-1", 30, 6, &full)).is_empty());
+
+        let bought = [Some("A"), None, Some("C"), Some("D"), Some("E")];
+        assert_eq!(
+            kinds(&mut detector, &state("3-1", 28, 6, &bought)),
+            vec![GameEventKind::ChampionBought {
+                champion_id: "B".to_string(),
+                cost: 2
+            }]
+        );
+
+        let rerolled = [Some("F"), Some("G"), Some("H"), Some("D"), Some("E")];
+        assert_eq!(
+            kinds(&mut detector, &state("3-1", 26, 6, &rerolled)),
+            vec![GameEventKind::ShopRerolled]
+        );
+
+        assert_eq!(
+            kinds(&mut detector, &state("3-1", 22, 7, &rerolled)),
+            vec![GameEventKind::LevelUp { level: 7 }, GameEventKind::XpPurchased]
+        );
+
+        assert_eq!(
+            kinds(&mut detector, &state("3-2", 29, 7, &full)),
+            vec![
+                GameEventKind::RoundEnded {
+                    round: "3-1".to_string(),
+                    outcome: None
+                },
+                GameEventKind::RoundStarted {
+                    round: "3-2".to_string()
+                },
+                GameEventKind::InterestGained { amount: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_purchases_costing_more_than_gold_drop() {
+        let mut detector = EventDetector::new();
+        let full = [Some("A"), Some("B"), Some("C"), Some("D"), Some("E")];
+        detector.observe(&state("3-1", 30, 6, &full), 0);
+
+        // Two 2-cost buys but gold only dropped by 1, e.g. a misread or a
+        // sale in the same frame: no XP purchase and no panic
+        let bought = [None, None, Some("C"), Some("D"), Some("E")];
+        let events = kinds(&mut detector, &state("3-1", 29, 6, &bought));
+        assert_eq!(events.len(), 2);
+        assert!(!events.contains(&GameEventKind::XpPurchased));
+    }
+
+    #[test]
+    fn test_round_events_round_trip() {
+        let events = [
+            GameEvent {
+                timestamp_ms: 500,
+                stage: "3-2".to_string(),
+                kind: GameEventKind::RoundEnded {
+                    round: "3-1".to_string(),
+                    outcome: Some(RoundOutcome::Win),
+                },
+            },
+            GameEvent {
+                timestamp_ms: 500,
+                stage: "3-2".to_string(),
+                kind: GameEventKind::RoundStarted {
+                    round: "3-2".to_string(),
+                },
+            },
+        ];
+        for event in events {
+            let json = serde_json::to_string(&event).unwrap();
+            assert_eq!(json.matches("\"stage\"").count(), 1);
+            let back: GameEvent = serde_json::from_str(&json).unwrap();
+            assert_eq!(back, event);
+        }
+    }
+
+    #[test]
+    fn test_star_up_and_item_combine() {
+        let unit = |col: u32, star_level: u32, items: Vec<Item>| BoardSlot {
+            row: 0,
+            col,
+            champion: Some(champion("Jinx", 1)),
+            star_level,
+            items,
+        };
+        let item = |id: &str, is_component: bool| Item {
+            id: id.to_string(),
+            name: id.to_string(),
+            is_component,
+        };
+
+        let recipes = HashMap::from([(
+            "Deathblade".to_string(),
+            ("BFSword".to_string(), "BFSword".to_string()),
+        )]);
+        let mut detector = EventDetector::with_recipes(recipes);
+        let mut before = GameState::new();
+        before.bench = vec![unit(0, 1, vec![item("BFSword", true)]), unit(1, 1, vec![])];
+        before.items = vec![item("BFSword", true)];
+        detector.observe(&before, 0);
+
+        let mut after = GameState::new();
+        after.bench = vec![unit(0, 2, vec![item("Deathblade", false)])];
+        let events = detector.observe(&after, 1000);
+        let kinds: Vec<_> = events.iter().map(|e| e.kind.clone()).collect();
+        assert!(kinds.contains(&GameEventKind::StarUp {
+            champion_id: "Jinx".to_string(),
+            star_level: 2
+        }));
+        assert!(kinds.contains(&GameEventKind::ItemCombined {
+            item_id: "Deathblade".to_string(),
+            components: vec!["BFSword".to_string(), "BFSword".to_string()]
+        }));
+        assert_eq!(events[0].timestamp_ms, 1000);
+
+        let json = serde_json::to_value(&events[0]).unwrap();
+        assert_eq!(json["type"], "starUp");
+        assert_eq!(json["championId"], "Jinx");
+    }
+
+    #[test]
+    fn test_two_items_combine_in_one_frame() {
+        let item = |id: &str, is_component: bool| Item {
+            id: id.to_string(),
+            name: id.to_string(),
+            is_component,
+        };
+        let recipe = |id: &str, a: &str, b: &str| (id.to_string(), (a.to_string(), b.to_string()));
+        let recipes = HashMap::from([
+            recipe("Bloodthirster", "BFSword", "NegatronCloak"),
+            recipe("Deathblade", "BFSword", "BFSword"),
+            recipe("HextechGunblade", "BFSword", "NeedlesslyLargeRod"),
+        ]);
+
+        let mut before = GameState::new();
+        before.items = ["NeedlesslyLargeRod", "BFSword", "NegatronCloak", "BFSword", "Spatula"]
+            .into_iter()
+            .map(|id| item(id, true))
+            .collect();
+        let mut after = GameState::new();
+        after.items = vec![
+            item("Spatula", true),
+            item("HextechGunblade", false),
+            item("Bloodthirster", false),
+        ];
+
+        // Sorting the lost components would have paired the two swords
+        let combined = |detector: &mut EventDetector| -> Vec<GameEventKind> {
+            detector.observe(&before, 0);
+            detector.observe(&after, 1000).into_iter().map(|e| e.kind).collect()
+        };
+        let components = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect();
+        assert_eq!(
+            combined(&mut EventDetector::with_recipes(recipes)),
+            vec![
+                GameEventKind::ItemCombined {
+                    item_id: "Bloodthirster".to_string(),
+                    components: components(&["BFSword", "NegatronCloak"]),
+                },
+                GameEventKind::ItemCombined {
+                    item_id: "HextechGunblade".to_string(),
+                    components: components(&["BFSword", "NeedlesslyLargeRod"]),
+                },
+            ]
+        );
+
+        // Without recipes the combines are still seen, but not what went in
+        let unknown = combined(&mut EventDetector::new());
+        assert_eq!(unknown.len(), 2);
+        assert!(unknown.iter().all(|k| matches!(
+            k,
+            GameEventKind::ItemCombined { components, .. } if components.is_empty()
+        )));
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{GameEvent, GameEventKind};
+use serde::{Deserialize, Serialize};
+
+/// Gold cost of one shop reroll
+const REROLL_COST: u32 = 2;
+
+/// Gold cost of one XP purchase
+const XP_COST: u32 = 4;
+
+/// Where the gold went during one round
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundLedger {
+    pub stage: String,
+    /// Gold held when the round started
+    pub start_gold: u32,
+    /// Interest paid out at the start of the round
+    pub interest: u32,
+    pub rerolls: u32,
+    pub xp_bought: u32,
+    /// Champion IDs bought this round, in order
+    pub units_bought: Vec<String>,
+    /// Gold spent on the units bought
+    pub unit_gold: u32,
+    /// Champion IDs sold this round, in order
+    pub units_sold: Vec<String>,
+    /// Gold held at the last read of the round
+    pub end_gold: u32,
+}
+
+impl RoundLedger {
+    pub fn reroll_gold(&self) -> u32 {
+        self.rerolls * REROLL_COST
+    }
+
+    pub fn xp_gold(&self) -> u32 {
+        self.xp_bought * XP_COST
+    }
+
+    /// Gold spent on rerolls, XP and units
+    pub fn spent(&self) -> u32 {
+        self.reroll_gold() + self.xp_gold() + self.unit_gold
+    }
+}
+
+/// Match-wide spending totals
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerSummary {
+    pub interest: u32,
+    pub rerolls: u32,
+    pub reroll_gold: u32,
+    pub xp_bought: u32,
+    pub xp_gold: u32,
+    pub units_bought: u32,
+    pub unit_gold: u32,
+    pub units_sold: u32,
+    /// Round with the most rerolls, if any were seen
+    pub biggest_roll_stage: Option<String>,
+}
+
+/// Per-round economy ledger built from game events and gold reads.
+#[derive(Debug, Clone, Default)]
+pub struct EconomyLedger {
+    rounds: Vec<RoundLedger>,
+}
+
+impl EconomyLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply the events of one state update, `gold` being the gold read in it
+    pub fn observe(&mut self, gold: Option<u32>, events: &[GameEvent]) {
+        for event in events {
+            if let GameEventKind::RoundStarted { round } = &event.kind {
+                self.rounds.push(RoundLedger {
+                    stage: round.clone(),
+                    start_gold: gold.unwrap_or(0),
+                    end_gold: gold.unwrap_or(0),
+                    ..Default::default()
+                });
+                continue;
+            }
+
+            let Some(round) = self.rounds.last_mut() else {
+                continue;
+            };
+            match &event.kind {
+                GameEventKind::InterestGained { amount } => round.interest += amount,
+                GameEventKind::ShopRerolled => round.rerolls += 1,
+                GameEventKind::XpPurchased => round.xp_bought += 1,
+                GameEventKind::ChampionBought { champion_id, cost } => {
+                    round.units_bought.push(champion_id.clone());
+                    round.unit_gold += cost;
+                }
+                GameEventKind::ChampionSold { champion_id } => {
+                    round.units_sold.push(champion_id.clone());
+                }
+                _ => {}
+            }
+        }
+
+        if let (Some(round), Some(gold)) = (self.rounds.last_mut(), gold) {
+            round.end_gold = gold;
+        }
+    }
+
+    /// Ledger of the given stage, e.g. "3-2"
+    pub fn round(&self, stage: &str) -> Option<&RoundLedger> {
+        self.rounds.iter().rev().find(|r| r.stage == stage)
+    }
+
+    /// All rounds in order
+    pub fn rounds(&self) -> &[RoundLedger] {
+        &self.rounds
+    }
+
+    pub fn summary(&self) -> LedgerSummary {
+        let mut summary = LedgerSummary::default();
+        for round in &self.rounds {
+            summary.interest += round.interest;
+            summary.rerolls += round.rerolls;
+            summary.reroll_gold += round.reroll_gold();
+            summary.xp_bought += round.xp_bought;
+            summary.xp_gold += round.xp_gold();
+            summary.units_bought += round.units_bought.len() as u32;
+            summary.unit_gold += round.unit_gold;
+            summary.units_sold += round.units_sold.len() as u32;
+        }
+        summary.biggest_roll_stage = self
+            .rounds
+            .iter()
+            .filter(|r| r.rerolls > 0)
+            .max_by_key(|r| r.rerolls)
+            .map(|r| r.stage.clone());
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: GameEventKind) -> GameEvent {
+        GameEvent {
+            timestamp_ms: 0,
+            stage: String::new(),
+            kind,
+        }
+    }
+
+    fn started(stage: &str) -> GameEvent {
+        event(GameEventKind::RoundStarted {
+            round: stage.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_ledger_tracks_spending_per_round() {
+        let mut ledger = EconomyLedger::new();
+        // Events before the first round start have nowhere to go
+        ledger.observe(Some(10), &[event(GameEventKind::ShopRerolled)]);
+        assert!(ledger.rounds().is_empty());
+
+        ledger.observe(
+            Some(42),
+            &[
+                started("4-1"),
+                event(GameEventKind::InterestGained { amount: 4 }),
+            ],
+        );
+        ledger.observe(Some(40), &[event(GameEventKind::ShopRerolled)]);
+        ledger.observe(Some(38), &[event(GameEventKind::ShopRerolled)]);
+        ledger.observe(
+            Some(34),
+            &[event(GameEventKind::ChampionBought {
+                champion_id: "Jinx".to_string(),
+                cost: 4,
+            })],
+        );
+        ledger.observe(Some(30), &[event(GameEventKind::XpPurchased)]);
+        // A missing gold read keeps the last one
+        ledger.observe(None, &[]);
+
+        ledger.observe(Some(36), &[started("4-2")]);
+        ledger.observe(
+            Some(38),
+            &[event(GameEventKind::ChampionSold {
+                champion_id: "Vi".to_string(),
+            })],
+        );
+
+        let round = ledger.round("4-1").unwrap();
+        assert_eq!(round.start_gold, 42);
+        assert_eq!(round.interest, 4);
+        assert_eq!(round.rerolls, 2);
+        assert_eq!(round.units_bought, vec!["Jinx".to_string()]);
+        assert_eq!(round.spent(), 4 + 4 + 4);
+        assert_eq!(round.end_gold, 30);
+        assert_eq!(ledger.round("4-2").unwrap().end_gold, 38);
+        assert!(ledger.round("5-1").is_none());
+
+        let summary = ledger.summary();
+        assert_eq!(summary.reroll_gold, 4);
+        assert_eq!(summary.xp_gold, 4);
+        assert_eq!(summary.units_sold, 1);
+        assert_eq!(summary.biggest_roll_stage.as_deref(), Some("4-1"));
+    }
+}
@@ -1,44 +1,223 @@
+mod augment;
+mod carousel;
+mod events;
+mod ledger;
+mod pool;
+mod round;
+mod smoothing;
+mod summary;
+mod timeline;
+
+pub use augment::{AugmentTracker, OfferedAugment};
+pub use carousel::{CarouselOffer, CarouselPick, CarouselTracker};
+pub use events::{EventDetector, GameEvent, GameEventKind};
+pub use ledger::{EconomyLedger, LedgerSummary, RoundLedger};
+pub use pool::{PoolEstimate, PoolTracker};
+pub use round::{RoundOutcome, RoundRecord, RoundTracker};
+pub use smoothing::{Readings, TemporalFilter, Voter};
+pub use summary::{EconomyPoint, MatchRecorder, MatchSummary};
+pub use timeline::{Timeline, TimelineEntry};
+
 use serde::{Deserialize, Serialize};
 
+/// Which part of the game the current frame shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GamePhase {
+    #[default]
+    Unknown,
+    /// Shop open between fights; all HUD reads are meaningful
+    Planning,
+    /// Fight in progress; HUD is visible but the board is in motion
+    Combat,
+    /// Shared draft; shop and HUD are hidden
+    Carousel,
+    /// Augment choice overlay on top of the planning phase
+    AugmentSelection,
+    /// Match loading screen
+    Loading,
+    /// Placement screen after the local player is eliminated or wins
+    PostGame,
+}
+
+impl GamePhase {
+    /// Whether shop, gold and level reads can be trusted in this phase. Frames
+    /// that could not be classified are not trusted.
+    pub fn has_hud(self) -> bool {
+        matches!(self, GamePhase::Planning | GamePhase::Combat)
+    }
+}
+
 /// Represents the full game state extracted from screen capture
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GameState {
+    pub phase: GamePhase,
     pub gold: u32,
     pub level: u32,
     pub stage: String,
+    /// Round within the stage (the 2 in "3-2"), 0 when the stage is unknown
+    pub round: u32,
     pub shop: Vec<ShopSlot>,
     pub bench: Vec<BoardSlot>,
     pub board: Vec<BoardSlot>,
     pub items: Vec<Item>,
+    pub hp: u32,
+    /// Signed streak: positive for wins, negative for losses
+    pub streak: i32,
+    /// Completed rounds, oldest first
+    pub rounds: Vec<RoundRecord>,
+    /// Augment IDs picked so far, in order
+    pub augments: Vec<String>,
+    /// Augments on the selection overlay, empty when it is not showing
+    pub augment_offer: Vec<OfferedAugment>,
+    /// Units on the carousel ring, empty outside carousel rounds
+    pub carousel: Vec<CarouselOffer>,
+    /// Units taken from carousels, in order
+    pub carousels: Vec<CarouselPick>,
+    /// Traits shown as active in the trait tracker
+    pub traits: Vec<ActiveTrait>,
+    /// Remaining-copies estimates for champions held, in the shop or seen on
+    /// scouted opponents
+    pub pool: Vec<PoolEstimate>,
+    /// Final placement, once the post-game screen was seen
+    pub placement: Option<u32>,
+}
+
+/// Champion identity and static data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Champion {
+    pub id: String,
+    pub name: String,
+    pub cost: u32,
+    pub traits: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ShopSlot {
     pub index: usize,
-    pub champion_id: Option<String>,
-    pub champion_name: Option<String>,
-    pub cost: Option<u32>,
+    pub champion: Option<Champion>,
     pub confidence: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BoardSlot {
+    /// Always 0 on the bench. On the board, 0 is the front row and 3 the back
+    /// row; odd rows sit half a hex to the right.
     pub row: u32,
     pub col: u32,
-    pub champion_id: Option<String>,
+    pub champion: Option<Champion>,
     pub star_level: u32,
     pub items: Vec<Item>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Item {
     pub id: String,
     pub name: String,
     pub is_component: bool,
 }
 
+/// Tier color of an active trait
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TraitTier {
+    Bronze,
+    Silver,
+    Gold,
+    Prismatic,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveTrait {
+    pub trait_id: String,
+    pub name: String,
+    pub count: u32,
+    pub tier: TraitTier,
+}
+
 impl GameState {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Whether two states show the player the same thing, ignoring the shop's
+    /// match confidences, which jitter from frame to frame
+    pub fn same_content(&self, other: &GameState) -> bool {
+        self.without_confidences() == other.without_confidences()
+    }
+
+    fn without_confidences(&self) -> GameState {
+        let mut state = self.clone();
+        state.shop.iter_mut().for_each(|s| s.confidence = 0.0);
+        state
+    }
+}
+
+/// Parse a stage label like "3-2" into (stage, round) for ordering comparisons.
+pub fn parse_stage(stage: &str) -> Option<(u32, u32)> {
+    let (major, minor) = stage.trim().split_once('-')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stage() {
+        assert_eq!(parse_stage("3-2"), Some((3, 2)));
+        assert_eq!(parse_stage(" 10-1 "), Some((10, 1)));
+        assert_eq!(parse_stage("3-"), None);
+        assert_eq!(parse_stage("32"), None);
+        assert!(parse_stage("4-1") > parse_stage("3-7"));
+    }
+
+    #[test]
+    fn test_same_content_ignores_confidence() {
+        let slot = |id: &str, confidence: f64| ShopSlot {
+            index: 0,
+            champion: Some(Champion {
+                id: id.to_string(),
+                name: id.to_string(),
+                cost: 1,
+                traits: Vec::new(),
+            }),
+            confidence,
+        };
+        let mut a = GameState::new();
+        a.shop = vec![slot("Vi", 0.81)];
+        let mut b = a.clone();
+        b.shop = vec![slot("Vi", 0.77)];
+        assert!(a.same_content(&b));
+        b.gold = 10;
+        assert!(!a.same_content(&b));
+    }
+
+    #[test]
+    fn test_has_hud() {
+        assert!(GamePhase::Planning.has_hud());
+        assert!(GamePhase::Combat.has_hud());
+        assert!(!GamePhase::Unknown.has_hud());
+        assert!(!GamePhase::Carousel.has_hud());
+    }
+
+    #[test]
+    fn test_game_state_serializes_camel_case() {
+        let mut state = GameState::new();
+        state.bench.push(BoardSlot {
+            row: 0,
+            col: 3,
+            champion: None,
+            star_level: 2,
+            items: Vec::new(),
+        });
+        let json = serde_json::to_value(&state).unwrap();
+        assert!(json.get("augmentOffer").is_some());
+        assert_eq!(json["bench"][0]["starLevel"], 2);
+        assert_eq!(json["phase"], "Unknown");
+    }
 }
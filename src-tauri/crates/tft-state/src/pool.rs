@@ -0,0 +1,166 @@
+use crate::{BoardSlot, GameState};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Remaining-copies estimate for one champion
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolEstimate {
+    pub champion_id: String,
+    /// Copies in the shared pool at the start of the game
+    pub total: u32,
+    /// Copies held by the local player
+    pub owned: u32,
+    /// Copies seen on scouted opponents' boards and benches
+    pub scouted: u32,
+    /// Copies that can still show up in shops
+    pub remaining: u32,
+}
+
+/// Estimates how many copies of each champion are left in the shared pool by
+/// subtracting the copies the local player holds and the copies last seen on
+/// each scouted opponent.
+#[derive(Debug, Clone, Default)]
+pub struct PoolTracker {
+    /// Champion ID → copies in the full pool
+    totals: HashMap<String, u32>,
+    owned: HashMap<String, u32>,
+    /// Opponent name → champion ID → copies
+    opponents: HashMap<String, HashMap<String, u32>>,
+}
+
+impl PoolTracker {
+    /// `totals` maps each champion ID to its copies in the full pool
+    pub fn new(totals: HashMap<String, u32>) -> Self {
+        Self {
+            totals,
+            ..Default::default()
+        }
+    }
+
+    /// Count the local player's copies from their bench and board
+    pub fn observe(&mut self, state: &GameState) {
+        // Skip frames where neither was read rather than forget everything
+        if state.bench.is_empty() && state.board.is_empty() {
+            return;
+        }
+        self.owned = copies(state.bench.iter().chain(&state.board));
+    }
+
+    /// Record what a scouted opponent holds, replacing their previous scout
+    pub fn observe_opponent(&mut self, opponent: &str, units: &[BoardSlot]) {
+        self.opponents
+            .insert(opponent.to_string(), copies(units.iter()));
+    }
+
+    /// Forget a player who was knocked out; their units return to the pool
+    pub fn remove_opponent(&mut self, opponent: &str) {
+        self.opponents.remove(opponent);
+    }
+
+    /// Estimate for one champion, `None` when its pool size is unknown
+    pub fn estimate(&self, champion_id: &str) -> Option<PoolEstimate> {
+        let total = *self.totals.get(champion_id)?;
+        let owned = self.owned.get(champion_id).copied().unwrap_or(0);
+        let scouted = self
+            .opponents
+            .values()
+            .filter_map(|units| units.get(champion_id))
+            .sum();
+        Some(PoolEstimate {
+            champion_id: champion_id.to_string(),
+            total,
+            owned,
+            scouted,
+            remaining: total.saturating_sub(owned + scouted),
+        })
+    }
+
+    /// Copies left in the pool, `None` when the pool size is unknown
+    pub fn remaining(&self, champion_id: &str) -> Option<u32> {
+        self.estimate(champion_id).map(|e| e.remaining)
+    }
+
+    /// Champion IDs seen on any scouted opponent
+    pub fn scouted(&self) -> impl Iterator<Item = &str> {
+        self.opponents
+            .values()
+            .flat_map(|units| units.keys())
+            .map(|id| id.as_str())
+    }
+
+    /// Estimates for the given champions, skipping unknown ones and duplicates
+    pub fn estimates_for<'a>(&self, ids: impl IntoIterator<Item = &'a str>) -> Vec<PoolEstimate> {
+        let mut estimates: Vec<PoolEstimate> = Vec::new();
+        for id in ids {
+            if estimates.iter().any(|e| e.champion_id == id) {
+                continue;
+            }
+            estimates.extend(self.estimate(id));
+        }
+        estimates
+    }
+}
+
+/// Champion ID → one-star copies, a 2-star being three and a 3-star nine
+fn copies<'a>(units: impl Iterator<Item = &'a BoardSlot>) -> HashMap<String, u32> {
+    let mut counts = HashMap::new();
+    for slot in units {
+        if let Some(champion) = &slot.champion {
+            *counts.entry(champion.id.clone()).or_insert(0) += 3u32.pow(slot.star_level.max(1) - 1);
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Champion;
+
+    fn unit(id: &str, star_level: u32) -> BoardSlot {
+        BoardSlot {
+            row: 0,
+            col: 0,
+            champion: Some(Champion {
+                id: id.to_string(),
+                name: id.to_string(),
+                cost: 2,
+                traits: Vec::new(),
+            }),
+            star_level,
+            items: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_remaining_copies() {
+        let totals = HashMap::from([("Vi".to_string(), 25), ("Jinx".to_string(), 25)]);
+        let mut tracker = PoolTracker::new(totals);
+
+        let mut state = GameState::new();
+        state.board = vec![unit("Vi", 2)];
+        state.bench = vec![unit("Vi", 1), unit("Jinx", 1)];
+        tracker.observe(&state);
+        tracker.observe_opponent("Opponent1", &[unit("Vi", 2), unit("Jinx", 1)]);
+        tracker.observe_opponent("Opponent2", &[unit("Vi", 1)]);
+
+        let vi = tracker.estimate("Vi").unwrap();
+        assert_eq!((vi.owned, vi.scouted, vi.remaining), (4, 4, 17));
+
+        // A fresh scout replaces the old one; knocked-out players release units
+        tracker.observe_opponent("Opponent1", &[unit("Vi", 3)]);
+        tracker.remove_opponent("Opponent2");
+        assert_eq!(tracker.remaining("Vi"), Some(12));
+        assert_eq!(tracker.remaining("Jinx"), Some(24));
+        assert_eq!(tracker.scouted().collect::<Vec<_>>(), vec!["Vi"]);
+
+        // Frames without a bench or board read keep the last count
+        tracker.observe(&GameState::new());
+        assert_eq!(tracker.remaining("Vi"), Some(12));
+
+        assert_eq!(tracker.remaining("Ahri"), None);
+        let estimates = tracker.estimates_for(["Jinx", "Ahri", "Jinx"]);
+        assert_eq!(estimates.len(), 1);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::parse_stage;
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a single combat round
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoundOutcome {
+    Win,
+    Loss,
+    /// Neither HP nor streak readings were available for the round
+    Unknown,
+}
+
+/// One completed round in the local player's history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundRecord {
+    pub stage: String,
+    pub outcome: RoundOutcome,
+    pub hp_before: Option<u32>,
+    pub hp_after: Option<u32>,
+    pub damage_taken: u32,
+    /// Signed streak after the round (positive = wins, negative = losses)
+    pub streak: i32,
+}
+
+/// Infers round outcomes from consecutive per-frame readings.
+///
+/// A round is closed when the stage advances. Damage is the HP lost between the
+/// first reading of the closed stage and the latest reading seen (damage lands at
+/// the end of combat, before the stage label changes). The streak flame, when
+/// visible, takes precedence over the HP delta for deciding win or loss, since
+/// PvE rounds and some augments can cost HP without being a loss streak.
+///
+/// The live streak drops to 0 on HUD frames without a flame, while round
+/// outcomes keep comparing the last flames seen.
+#[derive(Debug, Clone, Default)]
+pub struct RoundTracker {
+    stage: Option<String>,
+    hp_at_start: Option<u32>,
+    last_hp: Option<u32>,
+    streak_at_start: Option<i32>,
+    last_streak: Option<i32>,
+    /// Streak shown on the latest HUD frame
+    live_streak: i32,
+    history: Vec<RoundRecord>,
+}
+
+impl RoundTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one frame's readings; `hud` tells whether the frame shows the HUD
+    /// the streak flame sits in. Returns the record of the round that just
+    /// ended, if the stage advanced on this frame.
+    pub fn observe(
+        &mut self,
+        stage: Option<&str>,
+        hud: bool,
+        hp: Option<u32>,
+        streak: Option<i32>,
+    ) -> Option<&RoundRecord> {
+        let mut closed = false;
+
+        if let Some(stage) = stage.filter(|s| parse_stage(s).is_some()) {
+            match self.stage.as_deref() {
+                None => self.start_round(stage),
+                Some(current) if parse_stage(stage) > parse_stage(current) => {
+                    // Readings on the transition frame still belong to the old round
+                    if hp.is_some() {
+                        self.last_hp = hp;
+                    }
+                    if streak.is_some() {
+                        self.last_streak = streak;
+                    }
+                    self.close_round();
+                    self.start_round(stage);
+                    closed = true;
+                }
+                _ => {}
+            }
+        }
+
+        if hp.is_some() {
+            self.last_hp = hp;
+            if self.hp_at_start.is_none() {
+                self.hp_at_start = hp;
+            }
+        }
+        if streak.is_some() {
+            self.last_streak = streak;
+        }
+        if let Some(streak) = streak {
+            self.live_streak = streak;
+        } else if hud {
+            self.live_streak = 0;
+        }
+
+        if closed {
+            self.history.last()
+        } else {
+            None
+        }
+    }
+
+    /// All completed rounds, oldest first
+    pub fn history(&self) -> &[RoundRecord] {
+        &self.history
+    }
+
+    /// Current signed streak, 0 when none is shown
+    pub fn streak(&self) -> i32 {
+        self.live_streak
+    }
+
+    /// Latest known health
+    pub fn hp(&self) -> Option<u32> {
+        self.last_hp
+    }
+
+    fn start_round(&mut self, stage: &str) {
+        self.stage = Some(stage.to_string());
+        self.hp_at_start = self.last_hp;
+        self.streak_at_start = self.last_streak;
+    }
+
+    fn close_round(&mut self) {
+        let Some(stage) = self.stage.take() else {
+            return;
+        };
+
+        let damage_taken = match (self.hp_at_start, self.last_hp) {
+            (Some(before), Some(after)) => before.saturating_sub(after),
+            _ => 0,
+        };
+
+        let outcome = match (self.streak_at_start, self.last_streak) {
+            // A changed flame is the most direct evidence
+            (before, Some(after)) if before != Some(after) => {
+                if after > 0 {
+                    RoundOutcome::Win
+                } else {
+                    RoundOutcome::Loss
+                }
+            }
+            _ => match (self.hp_at_start, self.last_hp) {
+                (Some(_), Some(_)) if damage_taken > 0 => RoundOutcome::Loss,
+                (Some(_), Some(_)) => RoundOutcome::Win,
+                _ => RoundOutcome::Unknown,
+            },
+        };
+
+        self.history.push(RoundRecord {
+            stage,
+            outcome,
+            hp_before: self.hp_at_start,
+            hp_after: self.last_hp,
+            damage_taken,
+            streak: self.last_streak.unwrap_or(0),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loss_from_hp_drop() {
+        let mut tracker = RoundTracker::new();
+        tracker.observe(Some("2-1"), true, Some(100), None);
+        tracker.observe(Some("2-1"), true, Some(92), None);
+        let record = tracker.observe(Some("2-2"), true, Some(92), None).unwrap();
+        assert_eq!(record.stage, "2-1");
+        assert_eq!(record.outcome, RoundOutcome::Loss);
+        assert_eq!(record.damage_taken, 8);
+        assert_eq!(record.hp_after, Some(92));
+    }
+
+    #[test]
+    fn test_win_from_streak_and_misread_stage_ignored() {
+        let mut tracker = RoundTracker::new();
+        tracker.observe(Some("3-2"), true, Some(70), Some(2));
+        // A garbled or backwards stage read must not close the round
+        assert!(tracker
+            .observe(Some("3-"), true, Some(70), Some(2))
+            .is_none());
+        assert!(tracker
+            .observe(Some("2-5"), true, Some(70), Some(2))
+            .is_none());
+        let record = tracker
+            .observe(Some("3-3"), true, Some(70), Some(3))
+            .unwrap();
+        assert_eq!(record.outcome, RoundOutcome::Win);
+        assert_eq!(record.streak, 3);
+        assert_eq!(tracker.history().len(), 1);
+        assert_eq!(tracker.streak(), 3);
+    }
+
+    #[test]
+    fn test_streak_clears_without_flame() {
+        let mut tracker = RoundTracker::new();
+        tracker.observe(Some("3-1"), true, Some(80), Some(2));
+        tracker.observe(Some("3-2"), true, Some(80), Some(3));
+        // Carousel and augment frames have no HUD, so no flame says nothing
+        tracker.observe(Some("3-2"), false, None, None);
+        assert_eq!(tracker.streak(), 3);
+
+        // The streak broke: the HUD is back without a flame
+        tracker.observe(Some("3-2"), true, Some(80), None);
+        assert_eq!(tracker.streak(), 0);
+        tracker.observe(Some("3-2"), true, Some(71), None);
+        let record = tracker.observe(Some("3-3"), true, Some(71), None).unwrap();
+        // The round still compares the flames it saw, then falls back to HP
+        assert_eq!(record.outcome, RoundOutcome::Loss);
+        assert_eq!(record.damage_taken, 9);
+        assert_eq!(tracker.streak(), 0);
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{parse_stage, ShopSlot};
+use std::collections::{HashMap, VecDeque};
+
+/// Readings kept per field
+const DEFAULT_WINDOW: usize = 5;
+
+/// Consecutive frames a new value must win the vote before it is committed
+const DEFAULT_STABLE_FRAMES: usize = 3;
+
+/// Vote weight of a reading that breaks a domain constraint relative to the
+/// committed value. Such readings can still win, but only once they dominate
+/// the whole window.
+const IMPLAUSIBLE_WEIGHT: f64 = 0.2;
+
+/// Vote weight of an empty shop slot, which has no match confidence
+const EMPTY_SLOT_WEIGHT: f64 = 0.5;
+
+/// Largest gold change between two analysed frames that is taken at face value
+const MAX_GOLD_STEP: u32 = 20;
+
+/// Rolling, confidence-weighted majority vote over one field.
+#[derive(Debug, Clone)]
+pub struct Voter<T> {
+    window: VecDeque<(T, f64)>,
+    size: usize,
+    stable_frames: usize,
+    committed: Option<T>,
+    candidate: Option<(T, usize)>,
+    plausible: fn(&T, &T) -> bool,
+}
+
+impl<T: Clone + PartialEq> Voter<T> {
+    /// `plausible(committed, reading)` tells whether a reading is a believable
+    /// successor of the committed value.
+    pub fn new(size: usize, stable_frames: usize, plausible: fn(&T, &T) -> bool) -> Self {
+        Self {
+            window: VecDeque::with_capacity(size),
+            size: size.max(1),
+            stable_frames: stable_frames.max(1),
+            committed: None,
+            candidate: None,
+            plausible,
+        }
+    }
+
+    /// Add one reading and return the committed value.
+    pub fn push(&mut self, value: T, confidence: f64) -> Option<&T> {
+        let weight = match &self.committed {
+            Some(committed) if !(self.plausible)(committed, &value) => {
+                confidence * IMPLAUSIBLE_WEIGHT
+            }
+            _ => confidence,
+        };
+        self.window.push_back((value, weight));
+        while self.window.len() > self.size {
+            self.window.pop_front();
+        }
+
+        let winner = self.winner();
+        if winner.is_none() || winner == self.committed {
+            self.candidate = None;
+            return self.committed.as_ref();
+        }
+        let winner = winner.unwrap();
+
+        let count = match self.candidate.take() {
+            Some((value, count)) if value == winner => count + 1,
+            _ => 1,
+        };
+        if count >= self.stable_frames {
+            self.committed = Some(winner);
+        } else {
+            self.candidate = Some((winner, count));
+        }
+        self.committed.as_ref()
+    }
+
+    pub fn committed(&self) -> Option<&T> {
+        self.committed.as_ref()
+    }
+
+    /// Value with the highest total weight in the window; ties go to the most
+    /// recent reading.
+    fn winner(&self) -> Option<T> {
+        let mut best: Option<(&T, f64)> = None;
+        for (value, _) in self.window.iter().rev() {
+            let total: f64 = self
+                .window
+                .iter()
+                .filter(|(v, _)| v == value)
+                .map(|(_, w)| w)
+                .sum();
+            if best.is_none_or(|(_, b)| total > b) {
+                best = Some((value, total));
+            }
+        }
+        best.filter(|(_, total)| *total > 0.0)
+            .map(|(v, _)| v.clone())
+    }
+}
+
+/// The per-frame fields the filter smooths
+#[derive(Debug, Clone, Default)]
+pub struct Readings {
+    pub gold: Option<u32>,
+    pub level: Option<u32>,
+    pub stage: Option<String>,
+    /// Empty when the shop was not read on this frame
+    pub shop: Vec<ShopSlot>,
+}
+
+/// Smooths noisy per-frame reads so a single misread frame cannot flip a value.
+///
+/// Every field has its own voter. Missing reads (`None`, or an empty shop) are
+/// not votes: they leave the committed value in place.
+#[derive(Debug, Clone)]
+pub struct TemporalFilter {
+    window: usize,
+    stable_frames: usize,
+    gold: Voter<u32>,
+    level: Voter<u32>,
+    stage: Voter<String>,
+    shop: Vec<Voter<Option<String>>>,
+    /// Latest slot details seen for each champion, to rebuild committed slots
+    shop_details: HashMap<String, ShopSlot>,
+}
+
+impl Default for TemporalFilter {
+    fn default() -> Self {
+        Self::new(DEFAULT_WINDOW, DEFAULT_STABLE_FRAMES)
+    }
+}
+
+impl TemporalFilter {
+    pub fn new(window: usize, stable_frames: usize) -> Self {
+        Self {
+            window,
+            stable_frames,
+            gold: Voter::new(window, stable_frames, plausible_gold),
+            level: Voter::new(window, stable_frames, plausible_level),
+            // The stage only advances
+            stage: Voter::new(window, stable_frames, |committed, reading| {
+                parse_stage(reading) >= parse_stage(committed)
+            }),
+            shop: Vec::new(),
+            shop_details: HashMap::new(),
+        }
+    }
+
+    /// Feed one frame's raw reads and return the committed values.
+    pub fn update(&mut self, raw: Readings) -> Readings {
+        if let Some(gold) = raw.gold {
+            self.gold.push(gold, 1.0);
+        }
+        if let Some(level) = raw.level {
+            self.level.push(level, 1.0);
+        }
+        if let Some(stage) = raw.stage.filter(|s| parse_stage(s).is_some()) {
+            self.stage.push(stage, 1.0);
+        }
+
+        for slot in &raw.shop {
+            while self.shop.len() <= slot.index {
+                self.shop
+                    .push(Voter::new(self.window, self.stable_frames, |_, _| true));
+            }
+            let id = slot.champion.as_ref().map(|c| c.id.clone());
+            let weight = if id.is_some() {
+                slot.confidence
+            } else {
+                EMPTY_SLOT_WEIGHT
+            };
+            if let Some(id) = &id {
+                self.shop_details.insert(id.clone(), slot.clone());
+            }
+            self.shop[slot.index].push(id, weight);
+        }
+
+        let shop = if raw.shop.is_empty() {
+            Vec::new()
+        } else {
+            self.committed_shop()
+        };
+
+        Readings {
+            gold: self.gold.committed().copied(),
+            level: self.level.committed().copied(),
+            stage: self.stage.committed().cloned(),
+            shop,
+        }
+    }
+
+    fn committed_shop(&self) -> Vec<ShopSlot> {
+        self.shop
+            .iter()
+            .enumerate()
+            .map(|(index, voter)| {
+                let id = voter.committed().cloned().flatten();
+                match id.as_ref().and_then(|id| self.shop_details.get(id)) {
+                    Some(details) => ShopSlot {
+                        index,
+                        ..details.clone()
+                    },
+                    None => ShopSlot {
+                        index,
+                        champion: None,
+                        confidence: 0.0,
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+/// Gold moves by income, purchases, rerolls and sales, never by a huge jump
+/// between two analysed frames.
+fn plausible_gold(committed: &u32, reading: &u32) -> bool {
+    committed.abs_diff(*reading) <= MAX_GOLD_STEP
+}
+
+/// Levels only go up, one at a time.
+fn plausible_level(committed: &u32, reading: &u32) -> bool {
+    (1..=10).contains(reading) && (*reading == *committed || *reading == committed + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Champion;
+
+    fn gold(filter: &mut TemporalFilter, value: u32) -> Option<u32> {
+        filter
+            .update(Readings {
+                gold: Some(value),
+                ..Default::default()
+            })
+            .gold
+    }
+
+    #[test]
+    fn test_single_misread_does_not_flip_gold() {
+        let mut filter = TemporalFilter::default();
+        for _ in 0..3 {
+            gold(&mut filter, 50);
+        }
+        assert_eq!(gold(&mut filter, 50), Some(50));
+        assert_eq!(gold(&mut filter, 5), Some(50));
+        assert_eq!(gold(&mut filter, 50), Some(50));
+
+        // A genuine change commits once it has won the vote for three frames
+        assert_eq!(gold(&mut filter, 46), Some(50));
+        assert_eq!(gold(&mut filter, 46), Some(50));
+        assert_eq!(gold(&mut filter, 46), Some(50));
+        assert_eq!(gold(&mut filter, 46), Some(46));
+        // Missing reads keep the committed value
+        assert_eq!(filter.update(Readings::default()).gold, Some(46));
+    }
+
+    #[test]
+    fn test_stage_only_advances_and_shop_votes() {
+        let mut filter = TemporalFilter::default();
+        let slot = |id: Option<&str>, confidence: f64| ShopSlot {
+            index: 0,
+            champion: id.map(|id| Champion {
+                id: id.to_string(),
+                name: id.to_string(),
+                cost: 1,
+                traits: Vec::new(),
+            }),
+            confidence,
+        };
+        let frame = |stage: &str, id: Option<&str>, confidence: f64| Readings {
+            stage: Some(stage.to_string()),
+            shop: vec![slot(id, confidence)],
+            ..Default::default()
+        };
+
+        for _ in 0..3 {
+            filter.update(frame("3-2", Some("Jinx"), 0.8));
+        }
+        // A backwards stage and a low-confidence flip are both outvoted
+        let out = filter.update(frame("2-2", Some("Vi"), 0.45));
+        assert_eq!(out.stage.as_deref(), Some("3-2"));
+        assert_eq!(out.shop[0].champion.as_ref().unwrap().id, "Jinx");
+
+        // Buying the unit empties the slot for good
+        for _ in 0..4 {
+            filter.update(frame("3-2", None, 0.0));
+        }
+        assert!(filter.update(frame("3-2", None, 0.0)).shop[0]
+            .champion
+            .is_none());
+    }
+}
@@ -0,0 +1,189 @@
+use crate::timeline::{Timeline, TimelineEntry};
+use crate::{
+    BoardSlot, CarouselPick, EconomyLedger, GameEvent, LedgerSummary, RoundLedger, RoundRecord,
+};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Gold and level at the start of a round
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EconomyPoint {
+    pub stage: String,
+    pub timestamp_ms: u64,
+    pub gold: u32,
+    pub level: u32,
+}
+
+/// End-of-match report for one analysed game
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchSummary {
+    /// Final placement 1-8, when the post-game screen was seen
+    pub placement: Option<u32>,
+    pub final_stage: Option<String>,
+    pub final_board: Vec<BoardSlot>,
+    pub augments: Vec<String>,
+    pub carousels: Vec<CarouselPick>,
+    /// Gold/level curve, one point per round
+    pub economy: Vec<EconomyPoint>,
+    /// Per-round gold accounting
+    #[serde(default)]
+    pub ledger: Vec<RoundLedger>,
+    /// Match-wide spending totals
+    #[serde(default)]
+    pub spending: LedgerSummary,
+    pub rounds: Vec<RoundRecord>,
+    /// Time from the first to the last analysed frame
+    pub duration_ms: u64,
+    /// When the local player was knocked out (or the placement screen appeared)
+    pub eliminated_at_ms: Option<u64>,
+}
+
+impl MatchSummary {
+    /// Write the summary as pretty-printed JSON
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write match summary to {}", path.display()))
+    }
+}
+
+/// Accumulates per-frame readings over a video and builds the `MatchSummary`.
+#[derive(Debug, Clone, Default)]
+pub struct MatchRecorder {
+    timeline: Timeline,
+    economy: Vec<EconomyPoint>,
+    ledger: EconomyLedger,
+    last_gold: Option<u32>,
+    first_ms: Option<u64>,
+    last_ms: u64,
+    final_stage: Option<String>,
+    board: Vec<BoardSlot>,
+    carousels: Vec<CarouselPick>,
+    placement: Option<u32>,
+    eliminated_at_ms: Option<u64>,
+}
+
+impl MatchRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one analysed frame
+    pub fn record(&mut self, entry: TimelineEntry) {
+        self.first_ms.get_or_insert(entry.timestamp_ms);
+        self.last_ms = self.last_ms.max(entry.timestamp_ms);
+        self.last_gold = entry.gold;
+
+        if entry.hp == Some(0) && self.eliminated_at_ms.is_none() {
+            tracing::info!("Local player eliminated at {} ms", entry.timestamp_ms);
+            self.eliminated_at_ms = Some(entry.timestamp_ms);
+        }
+
+        if let Some(stage) = &entry.stage {
+            self.final_stage = Some(stage.clone());
+
+            // The first complete economy read of each round goes on the curve
+            let seen = self.economy.iter().any(|p| &p.stage == stage);
+            if let (false, Some(gold), Some(level)) = (seen, entry.gold, entry.level) {
+                self.economy.push(EconomyPoint {
+                    stage: stage.clone(),
+                    timestamp_ms: entry.timestamp_ms,
+                    gold,
+                    level,
+                });
+            }
+        }
+
+        self.timeline.push(entry);
+    }
+
+    /// Remember the latest recognised board, reported as the final board
+    pub fn record_board(&mut self, board: &[BoardSlot]) {
+        if !board.is_empty() {
+            self.board = board.to_vec();
+        }
+    }
+
+    /// Record game events in the timeline and the economy ledger. Call after
+    /// `record` for the same frame so the ledger sees its gold.
+    pub fn record_events(&mut self, events: &[GameEvent]) {
+        self.ledger.observe(self.last_gold, events);
+        self.timeline.push_events(events);
+    }
+
+    /// Record a unit taken from a carousel
+    pub fn record_carousel(&mut self, pick: CarouselPick) {
+        self.carousels.push(pick);
+    }
+
+    /// Record the placement read from the post-game screen
+    pub fn record_placement(&mut self, timestamp_ms: u64, placement: u32) {
+        if self.placement.is_none() {
+            tracing::info!("Final placement: {}", placement);
+        }
+        self.placement = Some(placement);
+        self.eliminated_at_ms.get_or_insert(timestamp_ms);
+    }
+
+    pub fn timeline(&self) -> &Timeline {
+        &self.timeline
+    }
+
+    pub fn ledger(&self) -> &EconomyLedger {
+        &self.ledger
+    }
+
+    /// Build the summary from everything recorded so far
+    pub fn summary(&self, augments: &[String], rounds: &[RoundRecord]) -> MatchSummary {
+        MatchSummary {
+            placement: self.placement,
+            final_stage: self.final_stage.clone(),
+            final_board: self.board.clone(),
+            augments: augments.to_vec(),
+            carousels: self.carousels.clone(),
+            economy: self.economy.clone(),
+            ledger: self.ledger.rounds().to_vec(),
+            spending: self.ledger.summary(),
+            rounds: rounds.to_vec(),
+            duration_ms: self.last_ms.saturating_sub(self.first_ms.unwrap_or(0)),
+            eliminated_at_ms: self.eliminated_at_ms,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(ms: u64, stage: &str, gold: u32, level: u32, hp: u32) -> TimelineEntry {
+        TimelineEntry {
+            timestamp_ms: ms,
+            stage: Some(stage.to_string()),
+            gold: Some(gold),
+            level: Some(level),
+            hp: Some(hp),
+        }
+    }
+
+    #[test]
+    fn test_summary_curve_and_elimination() {
+        let mut recorder = MatchRecorder::new();
+        recorder.record(entry(10_000, "2-1", 10, 4, 100));
+        recorder.record(entry(12_000, "2-1", 14, 4, 100));
+        recorder.record(entry(40_000, "2-2", 20, 5, 80));
+        recorder.record(entry(900_000, "4-5", 0, 8, 0));
+        recorder.record_placement(910_000, 6);
+
+        let summary = recorder.summary(&["TFT_Augment_A".to_string()], &[]);
+        assert_eq!(summary.placement, Some(6));
+        assert_eq!(summary.economy.len(), 3);
+        assert_eq!(summary.economy[0].gold, 10);
+        assert_eq!(summary.eliminated_at_ms, Some(900_000));
+        assert_eq!(summary.duration_ms, 890_000);
+        assert_eq!(summary.final_stage.as_deref(), Some("4-5"));
+        assert_eq!(recorder.timeline().entries.len(), 4);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{parse_stage, EconomyLedger, GameEvent, GameState};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// One analysed frame, positioned within the source video
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineEntry {
+    pub timestamp_ms: u64,
+    pub stage: Option<String>,
+    pub gold: Option<u32>,
+    pub level: Option<u32>,
+    pub hp: Option<u32>,
+}
+
+/// Per-frame record of a whole video analysis
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Timeline {
+    pub entries: Vec<TimelineEntry>,
+    /// Game events derived from consecutive states, in order
+    #[serde(default)]
+    pub events: Vec<GameEvent>,
+}
+
+impl Timeline {
+    pub fn push(&mut self, entry: TimelineEntry) {
+        self.entries.push(entry);
+    }
+
+    pub fn push_events(&mut self, events: &[GameEvent]) {
+        self.events.extend_from_slice(events);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Read a timeline written by `save`
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read timeline from {}", path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse timeline {}", path.display()))
+    }
+
+    /// The state at the start of each round: the first entry of every stage
+    /// that has a level read. Only the economy (stage, gold, level, HP) is
+    /// recorded per frame, so board, bench and shop are left empty.
+    pub fn round_states(&self) -> Vec<GameState> {
+        let mut states: Vec<GameState> = Vec::new();
+        for entry in &self.entries {
+            let (Some(stage), Some(level)) = (&entry.stage, entry.level) else {
+                continue;
+            };
+            if level == 0 || states.last().is_some_and(|s| &s.stage == stage) {
+                continue;
+            }
+            states.push(GameState {
+                stage: stage.clone(),
+                round: parse_stage(stage).map(|(_, round)| round).unwrap_or(0),
+                gold: entry.gold.unwrap_or(0),
+                level,
+                hp: entry.hp.unwrap_or(0),
+                ..Default::default()
+            });
+        }
+        states
+    }
+
+    /// Replay the events against the gold reads, as the match recorder did
+    pub fn ledger(&self) -> EconomyLedger {
+        let mut ledger = EconomyLedger::new();
+        let mut events = self.events.as_slice();
+        for entry in &self.entries {
+            let count = events
+                .iter()
+                .take_while(|e| e.timestamp_ms <= entry.timestamp_ms)
+                .count();
+            let (frame, rest) = events.split_at(count);
+            ledger.observe(entry.gold, frame);
+            events = rest;
+        }
+        ledger
+    }
+
+    /// Write the timeline as pretty-printed JSON
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write timeline to {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameEventKind;
+
+    fn entry(timestamp_ms: u64, stage: &str, gold: u32, level: u32) -> TimelineEntry {
+        TimelineEntry {
+            timestamp_ms,
+            stage: Some(stage.to_string()),
+            gold: Some(gold),
+            level: Some(level),
+            hp: Some(80),
+        }
+    }
+
+    fn event(timestamp_ms: u64, stage: &str, kind: GameEventKind) -> GameEvent {
+        GameEvent {
+            timestamp_ms,
+            stage: stage.to_string(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_rebuilds_rounds_from_saved_timeline() {
+        let mut timeline = Timeline::default();
+        for e in [
+            entry(1000, "3-2", 34, 0),
+            entry(2000, "3-2", 34, 5),
+            entry(3000, "3-2", 32, 5),
+            entry(4000, "3-3", 41, 5),
+        ] {
+            timeline.push(e);
+        }
+        timeline.push_events(&[
+            event(
+                2000,
+                "3-2",
+                GameEventKind::RoundStarted {
+                    round: "3-2".to_string(),
+                },
+            ),
+            event(3000, "3-2", GameEventKind::ShopRerolled),
+            event(
+                4000,
+                "3-3",
+                GameEventKind::RoundStarted {
+                    round: "3-3".to_string(),
+                },
+            ),
+        ]);
+
+        let dir =
+            std::env::temp_dir().join(format!("tft_state_test_timeline_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("timeline.json");
+        timeline.save(&path).unwrap();
+        let loaded = Timeline::load(&path);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let timeline = loaded.unwrap();
+
+        let states = timeline.round_states();
+        let rounds: Vec<(&str, u32, u32)> = states
+            .iter()
+            .map(|s| (s.stage.as_str(), s.gold, s.level))
+            .collect();
+        // The first 3-2 read has no level yet
+        assert_eq!(rounds, vec![("3-2", 34, 5), ("3-3", 41, 5)]);
+        assert_eq!(states[1].round, 3);
+
+        let ledger = timeline.ledger();
+        assert_eq!(ledger.rounds().len(), 2);
+        let round = ledger.round("3-2").unwrap();
+        assert_eq!(
+            (round.start_gold, round.rerolls, round.end_gold),
+            (34, 1, 32)
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use crate::phase::center_card_spans;
+use crate::template::{match_icon, IconTemplate};
+use crate::DigitReader;
+use anyhow::Result;
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::{debug, info, warn};
+
+/// Minimum NCC score for an icon match
+const MIN_ICON_CONFIDENCE: f64 = 0.5;
+
+/// Minimum title similarity (0-1) for an OCR match
+const MIN_TITLE_SIMILARITY: f64 = 0.75;
+
+/// How much brighter than the other cards the hovered card's frame must be
+const HIGHLIGHT_MARGIN: f64 = 1.12;
+
+/// One of the three cards on the augment selection screen
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AugmentCard {
+    pub slot: usize,
+    pub augment_id: Option<String>,
+    pub name: Option<String>,
+    pub confidence: f64,
+    /// The card is hovered or selected (its frame glows)
+    pub highlighted: bool,
+}
+
+/// Recognises augment cards by icon template, falling back to OCR of the title
+pub struct AugmentMatcher {
+    templates: Vec<IconTemplate>,
+    /// (normalized name, id, display name) for title matching
+    names: Vec<(String, String, String)>,
+}
+
+impl AugmentMatcher {
+    /// Load augment metadata and icon templates from the data directory.
+    /// Expects:
+    ///   - data_dir/augments.json (augment metadata)
+    ///   - data_dir/templates/augments/{icon} (icon images, optional)
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let game_data = tft_data::GameData::load(data_dir)?;
+        let templates_dir = data_dir.join("templates").join("augments");
+
+        let mut templates = Vec::new();
+        let mut names = Vec::new();
+
+        for (id, augment) in &game_data.augments {
+            names.push((normalize_title(&augment.name), id.clone(), augment.name.clone()));
+
+            let icon_path = templates_dir.join(&augment.icon);
+            if !icon_path.exists() {
+                continue;
+            }
+            match IconTemplate::load(&icon_path, id, &augment.name) {
+                Ok(template) => templates.push(template),
+                Err(e) => warn!("Failed to load augment template for {}: {}", id, e),
+            }
+        }
+
+        info!(
+            "AugmentMatcher loaded {} augments, {} icon templates",
+            names.len(),
+            templates.len()
+        );
+
+        Ok(Self { templates, names })
+    }
+
+    /// Match an augment icon crop against the loaded templates
+    pub fn match_icon(&self, icon: &RgbaImage) -> Option<(String, String, f64)> {
+        match_icon(&self.templates, icon, MIN_ICON_CONFIDENCE)
+    }
+
+    /// Match OCR'd title text against known augment names
+    pub fn match_title(&self, text: &str) -> Option<(String, String, f64)> {
+        let query = normalize_title(text);
+        if query.len() < 3 {
+            return None;
+        }
+
+        self.names
+            .iter()
+            .map(|(norm, id, name)| (id, name, similarity(&query, norm)))
+            .max_by(|a, b| a.2.partial_cmp(&b.2).unwrap())
+            .filter(|(_, _, score)| *score >= MIN_TITLE_SIMILARITY)
+            .map(|(id, name, score)| (id.clone(), name.clone(), score))
+    }
+
+    /// Number of known augments
+    pub fn augment_count(&self) -> usize {
+        self.names.len()
+    }
+}
+
+/// Locate and recognise the augment cards on the selection screen. Returns
+/// every card found, fewer than three while cards fade in or out after a
+/// pick; each keeps the slot of its position on screen.
+pub fn detect_augment_cards(
+    frame: &RgbaImage,
+    matcher: &AugmentMatcher,
+    digit_reader: &DigitReader,
+) -> Vec<AugmentCard> {
+    let spans = center_card_spans(frame);
+
+    let h = frame.height();
+    let card_top = h * 25 / 100;
+    let card_bottom = h * 70 / 100;
+    let card_h = card_bottom - card_top;
+
+    let frame_brightness: Vec<f64> = spans
+        .iter()
+        .map(|&(s, e)| border_brightness(frame, s as u32, e as u32, card_top, card_bottom))
+        .collect();
+
+    let mut cards = Vec::with_capacity(spans.len());
+    for (i, &(start, end)) in spans.iter().enumerate() {
+        let (x, w) = (start as u32, (end - start) as u32);
+        // The cards sit in the left, middle and right third of the frame
+        let slot = ((start + end) / 2 * 3 / frame.width() as usize).min(2);
+
+        // Icon: centered square in the upper part of the card
+        let icon_size = (w / 2).min(card_h / 3).max(1);
+        let icon = image::imageops::crop_imm(
+            frame,
+            x + (w - icon_size) / 2,
+            card_top + card_h / 10,
+            icon_size,
+            icon_size,
+        )
+        .to_image();
+
+        let mut recognised = matcher.match_icon(&icon);
+
+        // Title: a single text line below the icon
+        if recognised.is_none() {
+            let title_y = card_top + card_h / 10 + icon_size + card_h / 20;
+            let title = image::imageops::crop_imm(
+                frame,
+                x + w / 10,
+                title_y.min(h - 1),
+                w * 8 / 10,
+                (card_h / 10).max(1),
+            )
+            .to_image();
+            recognised = digit_reader
+                .read_text(&title)
+                .and_then(|text| matcher.match_title(&text));
+        }
+
+        let others: Vec<f64> = frame_brightness
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, b)| *b)
+            .collect();
+        let others_max = others.iter().cloned().fold(0.0, f64::max);
+        let highlighted = !others.is_empty() && frame_brightness[i] > others_max * HIGHLIGHT_MARGIN;
+
+        debug!(
+            "Augment card {}: {:?} (frame brightness {:.1}{})",
+            slot,
+            recognised.as_ref().map(|r| &r.1),
+            frame_brightness[i],
+            if highlighted { ", highlighted" } else { "" }
+        );
+
+        cards.push(AugmentCard {
+            slot,
+            augment_id: recognised.as_ref().map(|r| r.0.clone()),
+            name: recognised.as_ref().map(|r| r.1.clone()),
+            confidence: recognised.as_ref().map(|r| r.2).unwrap_or(0.0),
+            highlighted,
+        });
+    }
+
+    cards
+}
+
+/// Mean brightness of the outer ring of a card, where the hover glow shows.
+fn border_brightness(frame: &RgbaImage, x0: u32, x1: u32, y0: u32, y1: u32) -> f64 {
+    let ring = ((x1 - x0) / 20).max(2);
+    let mut sum = 0.0;
+    let mut count = 0u32;
+    for y in y0..y1 {
+        for x in x0..x1 {
+            let on_ring = x < x0 + ring || x >= x1 - ring || y < y0 + ring || y >= y1 - ring;
+            if on_ring {
+                let px = frame.get_pixel(x, y);
+                sum += (px[0] as f64 + px[1] as f64 + px[2] as f64) / 3.0;
+                count += 1;
+            }
+        }
+    }
+    sum / count.max(1) as f64
+}
+
+/// Lowercase and keep only letters and digits, so OCR punctuation noise is ignored
+fn normalize_title(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// Normalized Levenshtein similarity in 0.0..=1.0
+fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+
+    1.0 - prev[b.len()] as f64 / longest as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher_with_names(names: &[(&str, &str)]) -> AugmentMatcher {
+        AugmentMatcher {
+            templates: Vec::new(),
+            names: names
+                .iter()
+                .map(|(id, name)| (normalize_title(name), id.to_string(), name.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_match_title_tolerates_ocr_noise() {
+        let matcher = matcher_with_names(&[
+            ("TFT_Augment_PandorasItems", "Pandora's Items"),
+            ("TFT_Augment_TradeSector", "Trade Sector"),
+        ]);
+        let (id, _, score) = matcher.match_title("Pand0ras ltems").unwrap();
+        assert_eq!(id, "TFT_Augment_PandorasItems");
+        assert!(score > 0.8);
+        assert!(matcher.match_title("Combat Training").is_none());
+    }
+
+    #[test]
+    fn test_detect_cards_marks_highlight() {
+        // Three cards on a dark overlay; the middle one has a brighter frame
+        let spans = [(40u32, 120u32), (160, 240), (280, 360)];
+        let frame = card_frame(&spans);
+        let matcher = matcher_with_names(&[]);
+        let cards = detect_augment_cards(&frame, &matcher, &DigitReader::new());
+        assert_eq!(cards.len(), 3);
+        assert!(cards[1].highlighted);
+        assert!(!cards[0].highlighted && !cards[2].highlighted);
+
+        // The picked card is the last one standing and keeps its slot
+        let cards = detect_augment_cards(&card_frame(&spans[2..]), &matcher, &DigitReader::new());
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].slot, 2);
+        assert!(!cards[0].highlighted);
+    }
+
+    fn card_frame(spans: &[(u32, u32)]) -> RgbaImage {
+        RgbaImage::from_fn(400, 200, |x, _| {
+            match spans.iter().position(|&(s, e)| (s..e).contains(&x)) {
+                Some(i) => {
+                    let (s, e) = spans[i];
+                    let edge = x < s + 4 || x >= e - 4;
+                    if i == 1 && edge {
+                        image::Rgba([250, 240, 200, 255])
+                    } else {
+                        image::Rgba([150, 140, 130, 255])
+                    }
+                }
+                None => image::Rgba([15, 15, 20, 255]),
+            }
+        })
+    }
+}
@@ -0,0 +1,68 @@
+use crate::ChampionMatcher;
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+/// Number of bench slots
+const BENCH_SLOTS: usize = 9;
+
+/// A recognised unit on the bench
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchUnit {
+    pub slot: usize,
+    pub champion_id: String,
+    pub champion_name: String,
+    pub confidence: f64,
+}
+
+/// Read the bench row just above the HUD. Empty slots are left out.
+pub fn read_bench(frame: &RgbaImage, hud_top: f64, matcher: &ChampionMatcher) -> Vec<BenchUnit> {
+    let units: Vec<BenchUnit> = bench_slots(frame.width(), frame.height(), hud_top)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(slot, (x, y, w, h))| {
+            let crop = image::imageops::crop_imm(frame, x, y, w, h).to_image();
+            matcher.match_champion(&crop).map(|m| BenchUnit {
+                slot,
+                champion_id: m.champion_id,
+                champion_name: m.champion_name,
+                confidence: m.confidence,
+            })
+        })
+        .collect();
+    debug!("Bench: {} unit(s) recognised", units.len());
+    units
+}
+
+/// Pixel rectangles (x, y, w, h) of the bench slots. The bench spans the
+/// middle 60% of the screen in a band about 10% of the height above the HUD.
+fn bench_slots(w: u32, h: u32, hud_top: f64) -> Vec<(u32, u32, u32, u32)> {
+    let bottom = ((hud_top - 0.01).clamp(0.1, 1.0) * h as f64) as u32;
+    let top = ((hud_top - 0.11).clamp(0.0, 1.0) * h as f64) as u32;
+    let slot_h = (bottom.saturating_sub(top)).max(1);
+    let x0 = w as f64 * 0.19;
+    let slot_w = w as f64 * 0.6 / BENCH_SLOTS as f64;
+
+    (0..BENCH_SLOTS)
+        .map(|i| {
+            let x = (x0 + i as f64 * slot_w) as u32;
+            (x, top.min(h - slot_h), (slot_w as u32).max(1), slot_h)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bench_slots_sit_above_hud() {
+        let slots = bench_slots(1920, 1080, 0.8);
+        assert_eq!(slots.len(), BENCH_SLOTS);
+        for (x, y, w, h) in &slots {
+            assert!(x + w <= 1920);
+            assert!(y + h <= 864);
+        }
+        assert!(slots[0].0 < slots[8].0);
+    }
+}
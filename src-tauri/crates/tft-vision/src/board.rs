@@ -0,0 +1,90 @@
+use crate::ChampionMatcher;
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+/// Hex rows on the local player's half of the board
+const BOARD_ROWS: u32 = 4;
+/// Hexes per row
+const BOARD_COLS: u32 = 7;
+
+/// Pixel rectangle (x, y, w, h)
+type Rect = (u32, u32, u32, u32);
+
+/// A recognised unit on the local player's board
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardUnit {
+    /// 0 is the front row, facing the opponent; 3 is the back row
+    pub row: u32,
+    pub col: u32,
+    pub champion_id: String,
+    pub champion_name: String,
+    pub confidence: f64,
+}
+
+/// Read the local player's half of the board, between the bench and the
+/// middle of the arena. Empty hexes are left out.
+pub fn read_board(frame: &RgbaImage, hud_top: f64, matcher: &ChampionMatcher) -> Vec<BoardUnit> {
+    let units: Vec<BoardUnit> = board_hexes(frame.width(), frame.height(), hud_top)
+        .into_iter()
+        .filter_map(|(row, col, (x, y, w, h))| {
+            let crop = image::imageops::crop_imm(frame, x, y, w, h).to_image();
+            matcher.match_champion(&crop).map(|m| BoardUnit {
+                row,
+                col,
+                champion_id: m.champion_id,
+                champion_name: m.champion_name,
+                confidence: m.confidence,
+            })
+        })
+        .collect();
+    debug!("Board: {} unit(s) recognised", units.len());
+    units
+}
+
+/// Pixel rectangles of the board hexes with their row and column.
+/// The four rows fill a band about 32% of the height above the bench; rows
+/// narrow towards the front with the camera's perspective, and odd rows sit
+/// half a hex to the right of even rows.
+fn board_hexes(w: u32, h: u32, hud_top: f64) -> Vec<(u32, u32, Rect)> {
+    let bottom = (hud_top - 0.12).clamp(0.1, 1.0);
+    let top = (hud_top - 0.44).clamp(0.0, bottom);
+    let row_h = (bottom - top) / BOARD_ROWS as f64;
+    let slot_h = ((row_h * h as f64) as u32).max(1);
+
+    (0..BOARD_ROWS)
+        .flat_map(|row| {
+            // Row 3 is nearest the camera and the widest
+            let width = 0.44 + 0.03 * row as f64;
+            let hex_w = width / (BOARD_COLS as f64 + 0.5);
+            let x0 = 0.5 - width / 2.0 + if row % 2 == 1 { hex_w / 2.0 } else { 0.0 };
+            let y = ((top + row as f64 * row_h) * h as f64) as u32;
+            (0..BOARD_COLS).map(move |col| {
+                let x = ((x0 + col as f64 * hex_w) * w as f64) as u32;
+                let slot_w = ((hex_w * w as f64) as u32).max(1);
+                (row, col, (x, y.min(h - slot_h), slot_w, slot_h))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_board_hexes_sit_above_bench() {
+        let hexes = board_hexes(1920, 1080, 0.8);
+        assert_eq!(hexes.len(), (BOARD_ROWS * BOARD_COLS) as usize);
+        for (_, _, (x, y, w, h)) in &hexes {
+            assert!(x + w <= 1920);
+            // The bench starts 11% of the height above the HUD
+            assert!(y + h <= 745);
+        }
+        let at = |row: u32, col: u32| hexes[(row * BOARD_COLS + col) as usize].2;
+        // The back row is nearest the bench, odd rows are shifted right
+        assert!(at(3, 0).1 > at(0, 0).1);
+        assert!(at(1, 0).0 > at(0, 0).0);
+        assert!(at(0, 0).0 < at(0, 6).0);
+    }
+}
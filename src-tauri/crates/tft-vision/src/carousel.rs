@@ -0,0 +1,143 @@
+use crate::{ChampionMatcher, ItemMatcher};
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+/// Number of sample positions around the ring. The carousel rotates, so we
+/// sample twice as many angles as there are units and merge duplicates.
+const RING_SAMPLES: usize = 18;
+
+/// One unit standing on the carousel ring
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CarouselUnit {
+    pub champion_id: String,
+    pub champion_name: String,
+    /// Item the unit carries, when its icon was recognised
+    pub item_id: Option<String>,
+    pub item_name: Option<String>,
+    pub confidence: f64,
+}
+
+/// Identify the champions and items on the carousel ring.
+/// Each unit is reported once, with its best-scoring read.
+pub fn detect_carousel_units(
+    frame: &RgbaImage,
+    matcher: &ChampionMatcher,
+    item_matcher: &ItemMatcher,
+) -> Vec<CarouselUnit> {
+    let mut units = Vec::new();
+
+    for (x, y, size) in ring_patches(frame.width(), frame.height()) {
+        let patch = image::imageops::crop_imm(frame, x, y, size, size).to_image();
+        let Some(champion) = matcher.match_champion(&patch) else {
+            continue;
+        };
+
+        // The held item floats just below the unit
+        let item_size = (size / 2).max(1);
+        let item_y = (y + size).min(frame.height() - item_size);
+        let item_icon = image::imageops::crop_imm(
+            frame,
+            x + (size - item_size) / 2,
+            item_y,
+            item_size,
+            item_size,
+        )
+        .to_image();
+        let item = item_matcher.match_item(&item_icon);
+
+        units.push(CarouselUnit {
+            champion_id: champion.champion_id,
+            champion_name: champion.champion_name,
+            item_id: item.as_ref().map(|i| i.0.clone()),
+            item_name: item.as_ref().map(|i| i.1.clone()),
+            confidence: champion.confidence,
+        });
+    }
+
+    let units = merge_units(units);
+    debug!("Carousel: {} unit(s) recognised", units.len());
+    units
+}
+
+/// Square patches (x, y, size) centred on evenly spaced points of the carousel
+/// ring, an ellipse around the middle of the arena.
+fn ring_patches(w: u32, h: u32) -> Vec<(u32, u32, u32)> {
+    let size = (w * 7 / 100).max(1).min(h);
+    let (cx, cy) = (w as f64 * 0.5, h as f64 * 0.47);
+    let (rx, ry) = (w as f64 * 0.22, h as f64 * 0.25);
+
+    (0..RING_SAMPLES)
+        .map(|i| {
+            let angle = i as f64 / RING_SAMPLES as f64 * std::f64::consts::TAU;
+            let px = cx + rx * angle.cos() - size as f64 / 2.0;
+            let py = cy + ry * angle.sin() - size as f64 / 2.0;
+            let x = (px.max(0.0) as u32).min(w - size);
+            let y = (py.max(0.0) as u32).min(h - size);
+            (x, y, size)
+        })
+        .collect()
+}
+
+/// Keep one entry per champion and item pair, preferring the most confident
+/// read; a read without an item is dropped when the same champion was also
+/// seen carrying one.
+fn merge_units(units: Vec<CarouselUnit>) -> Vec<CarouselUnit> {
+    let mut merged: Vec<CarouselUnit> = Vec::new();
+    for unit in units {
+        let existing = merged.iter_mut().find(|m| {
+            m.champion_id == unit.champion_id
+                && (m.item_id == unit.item_id || m.item_id.is_none() || unit.item_id.is_none())
+        });
+        match existing {
+            Some(m) => {
+                if unit.item_id.is_some() && m.item_id.is_none() {
+                    m.item_id = unit.item_id;
+                    m.item_name = unit.item_name;
+                }
+                m.confidence = m.confidence.max(unit.confidence);
+            }
+            None => merged.push(unit),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit(id: &str, item: Option<&str>, confidence: f64) -> CarouselUnit {
+        CarouselUnit {
+            champion_id: id.to_string(),
+            champion_name: id.to_string(),
+            item_id: item.map(String::from),
+            item_name: item.map(String::from),
+            confidence,
+        }
+    }
+
+    #[test]
+    fn test_merge_units_dedups_rotated_reads() {
+        let merged = merge_units(vec![
+            unit("Jinx", None, 0.5),
+            unit("Jinx", Some("BFSword"), 0.6),
+            unit("Vi", Some("Tear"), 0.7),
+            // Two copies of a champion with different items are distinct units
+            unit("Vi", Some("Bow"), 0.55),
+        ]);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].item_id.as_deref(), Some("BFSword"));
+        assert_eq!(merged[0].confidence, 0.6);
+    }
+
+    #[test]
+    fn test_ring_patches_stay_in_frame() {
+        let (w, h) = (1280, 720);
+        let patches = ring_patches(w, h);
+        assert_eq!(patches.len(), RING_SAMPLES);
+        for (x, y, size) in patches {
+            assert!(x + size <= w && y + size <= h);
+        }
+    }
+}
@@ -5,7 +5,7 @@ use std::path::Path;
 use tracing::{debug, info, warn};
 
 /// Standard size for template matching (both templates and crops are resized to this)
-const MATCH_SIZE: u32 = 48;
+pub(crate) const MATCH_SIZE: u32 = 48;
 
 /// Minimum confidence to consider a match valid
 const MIN_CONFIDENCE: f64 = 0.4;
@@ -152,7 +152,7 @@ fn load_template(path: &Path, id: &str, name: &str, cost: u32) -> Result<Champio
 }
 
 /// Compute mean and standard deviation of pixel values
-fn compute_stats(img: &GrayImage) -> (f64, f64) {
+pub(crate) fn compute_stats(img: &GrayImage) -> (f64, f64) {
     let pixels: Vec<f64> = img.pixels().map(|p| p[0] as f64).collect();
     let n = pixels.len() as f64;
     if n == 0.0 {
@@ -165,7 +165,7 @@ fn compute_stats(img: &GrayImage) -> (f64, f64) {
 
 /// Zero-mean Normalized Cross-Correlation between two same-sized images.
 /// Returns a value between -1.0 (inverse) and 1.0 (perfect match).
-fn normalized_cross_correlation(
+pub(crate) fn normalized_cross_correlation(
     img: &GrayImage,
     img_mean: f64,
     img_std: f64,
@@ -66,6 +66,33 @@ impl DigitReader {
         }
     }
 
+    /// Read a trait counter like "3/5" (or a bare "3") from a cropped region.
+    /// Returns the current count and, when shown, the next breakpoint.
+    pub fn read_ratio(&self, image: &RgbaImage) -> Option<(u32, Option<u32>)> {
+        if !self.tesseract_available {
+            return None;
+        }
+
+        let processed = preprocess_for_ocr(image);
+        self.save_debug_ocr(image, &processed, "ratio");
+        let text = self.run_tesseract(&processed, "0123456789/")?;
+        parse_ratio(&text)
+    }
+
+    /// Read a single line of free text (e.g., an augment title) from a cropped region
+    pub fn read_text(&self, image: &RgbaImage) -> Option<String> {
+        if !self.tesseract_available {
+            return None;
+        }
+
+        let processed = preprocess_for_ocr(image);
+        self.save_debug_ocr(image, &processed, "text");
+        self.run_tesseract(
+            &processed,
+            "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789 '!-.&",
+        )
+    }
+
     /// Save debug OCR images (raw + processed) for the first few invocations
     fn save_debug_ocr(&self, raw: &RgbaImage, processed: &GrayImage, label: &str) {
         let count = OCR_DEBUG_COUNTER.fetch_add(1, Ordering::Relaxed);
@@ -171,6 +198,14 @@ fn preprocess_for_ocr(image: &RgbaImage) -> GrayImage {
     })
 }
 
+/// Parse "3/5" or "3" into (current, next breakpoint)
+fn parse_ratio(text: &str) -> Option<(u32, Option<u32>)> {
+    match text.split_once('/') {
+        Some((current, next)) => Some((current.trim().parse().ok()?, next.trim().parse().ok())),
+        None => Some((text.trim().parse().ok()?, None)),
+    }
+}
+
 /// Check if Tesseract is installed and accessible
 fn check_tesseract() -> bool {
     Command::new("tesseract")
@@ -197,4 +232,11 @@ mod tests {
         assert_eq!(processed.get_pixel(0, 0)[0], 0); // text is black
         assert_eq!(processed.get_pixel(9, 0)[0], 255); // bg is white
     }
+
+    #[test]
+    fn test_parse_ratio() {
+        assert_eq!(parse_ratio("3/5"), Some((3, Some(5))));
+        assert_eq!(parse_ratio("1"), Some((1, None)));
+        assert_eq!(parse_ratio("/5"), None);
+    }
 }
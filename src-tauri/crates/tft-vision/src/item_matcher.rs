@@ -0,0 +1,55 @@
+use crate::template::{match_icon, IconTemplate};
+use anyhow::Result;
+use image::RgbaImage;
+use std::path::Path;
+use tracing::{debug, info, warn};
+
+/// Minimum NCC score for an item icon match
+const MIN_ITEM_CONFIDENCE: f64 = 0.5;
+
+/// Matches item icons (carousel items, item bench) against item templates
+pub struct ItemMatcher {
+    templates: Vec<IconTemplate>,
+}
+
+impl ItemMatcher {
+    /// Load item icon templates from the data directory.
+    /// Expects:
+    ///   - data_dir/items.json (item metadata)
+    ///   - data_dir/templates/items/{icon} (icon images)
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let game_data = tft_data::GameData::load(data_dir)?;
+        let templates_dir = data_dir.join("templates").join("items");
+
+        let mut templates = Vec::new();
+        for (id, item) in &game_data.items {
+            let icon_path = templates_dir.join(&item.icon);
+            if item.icon.is_empty() || !icon_path.exists() {
+                debug!("Missing icon for item {}: {}", id, icon_path.display());
+                continue;
+            }
+            match IconTemplate::load(&icon_path, id, &item.name) {
+                Ok(template) => templates.push(template),
+                Err(e) => warn!("Failed to load item template for {}: {}", id, e),
+            }
+        }
+
+        info!(
+            "ItemMatcher loaded {} templates from {}",
+            templates.len(),
+            templates_dir.display()
+        );
+
+        Ok(Self { templates })
+    }
+
+    /// Match an item icon crop. Returns (id, name, confidence) of the best match.
+    pub fn match_item(&self, icon: &RgbaImage) -> Option<(String, String, f64)> {
+        match_icon(&self.templates, icon, MIN_ITEM_CONFIDENCE)
+    }
+
+    /// Number of loaded templates
+    pub fn template_count(&self) -> usize {
+        self.templates.len()
+    }
+}
@@ -9,6 +9,10 @@ pub struct DetectedLayout {
     pub gold: Option<ScreenRegion>,
     pub level: Option<ScreenRegion>,
     pub stage: Option<ScreenRegion>,
+    /// Streak flame icon plus its counter, just right of the gold display
+    pub streak: Option<ScreenRegion>,
+    /// Local player's health in the right-side scoreboard
+    pub health: Option<ScreenRegion>,
     /// Y coordinate (normalized) of the HUD top boundary
     pub hud_top: f64,
 }
@@ -54,11 +58,21 @@ pub fn detect_layout(frame: &RgbaImage) -> DetectedLayout {
     // 6. Find stage text at top center
     let stage = find_stage_region(frame);
 
+    // 7. Find the streak flame next to the gold display
+    let streak = gold
+        .as_ref()
+        .and_then(|g| find_streak_region(frame, g, bar_search_top, bar_search_bottom));
+
+    // 8. Find the local player's health in the scoreboard
+    let health = find_health_region(frame);
+
     DetectedLayout {
         shop_slots,
         gold,
         level,
         stage,
+        streak,
+        health,
         hud_top,
     }
 }
@@ -214,7 +228,7 @@ fn find_shop_cards(frame: &RgbaImage, hud_top: u32) -> Vec<ScreenRegion> {
         .collect()
 }
 
-fn smooth(data: &[f64], window: usize) -> Vec<f64> {
+pub(crate) fn smooth(data: &[f64], window: usize) -> Vec<f64> {
     let half = window / 2;
     (0..data.len())
         .map(|i| {
@@ -235,7 +249,7 @@ fn adaptive_threshold(profile: &[f64]) -> f64 {
 }
 
 /// Find contiguous bright segments above the threshold.
-fn find_bright_segments(
+pub(crate) fn find_bright_segments(
     profile: &[f64],
     threshold: f64,
     min_width: usize,
@@ -520,6 +534,156 @@ fn find_stage_region(frame: &RgbaImage) -> Option<ScreenRegion> {
     })
 }
 
+/// Find the win/loss streak indicator to the right of the gold number.
+///
+/// The indicator is a flame icon (orange-red for win streaks, blue for loss streaks)
+/// followed by the streak count. It is only drawn for streaks of 2 or more, so
+/// `None` is the normal result for a player without a streak.
+fn find_streak_region(
+    frame: &RgbaImage,
+    gold: &ScreenRegion,
+    bar_top: u32,
+    bar_bottom: u32,
+) -> Option<ScreenRegion> {
+    let (w, h) = (frame.width(), frame.height());
+    let wf = w as f64;
+    let hf = h as f64;
+
+    if bar_top >= bar_bottom {
+        return None;
+    }
+
+    // Search a strip starting at the right edge of the gold text
+    let search_x_start = ((gold.x + gold.width) * wf) as u32;
+    let search_x_end = (search_x_start + (wf * 0.06) as u32).min(w);
+    if search_x_start >= search_x_end {
+        return None;
+    }
+
+    let mut flame_xs: Vec<u32> = Vec::new();
+    let mut flame_ys: Vec<u32> = Vec::new();
+
+    for y in bar_top..bar_bottom.min(h) {
+        for x in search_x_start..search_x_end {
+            let px = frame.get_pixel(x, y);
+            if crate::streak::is_fire_pixel(px) || crate::streak::is_ice_pixel(px) {
+                flame_xs.push(x);
+                flame_ys.push(y);
+            }
+        }
+    }
+
+    if flame_xs.len() < 12 {
+        debug!(
+            "No streak flame found right of gold ({} flame pixels)",
+            flame_xs.len()
+        );
+        return None;
+    }
+
+    flame_xs.sort();
+    flame_ys.sort();
+    let x_min = flame_xs[0];
+    let x_max = flame_xs[flame_xs.len() - 1];
+    let y_min = flame_ys[0];
+    let y_max = flame_ys[flame_ys.len() - 1];
+
+    // The counter sits immediately right of the flame and is about as wide
+    let flame_w = (x_max - x_min).max(6);
+    let pad = 3u32;
+    let region_x = x_min.saturating_sub(pad);
+    let region_y = y_min.saturating_sub(pad);
+    let region_w = (flame_w * 2 + pad * 2).min(w - region_x);
+    let region_h = (y_max - y_min + pad * 2).max(12).min(h - region_y);
+
+    debug!(
+        "Streak flame at x={}..{}, y={}..{} ({} flame pixels)",
+        x_min,
+        x_max,
+        y_min,
+        y_max,
+        flame_xs.len()
+    );
+
+    Some(ScreenRegion {
+        x: region_x as f64 / wf,
+        y: region_y as f64 / hf,
+        width: region_w as f64 / wf,
+        height: region_h as f64 / hf,
+    })
+}
+
+/// Find the local player's health in the scoreboard on the right edge.
+///
+/// The scoreboard lists all eight players top to bottom; the local player's entry
+/// is drawn with a gold highlight. We locate the row with the most gold pixels and
+/// return the number area on its right side, where the health value is printed.
+fn find_health_region(frame: &RgbaImage) -> Option<ScreenRegion> {
+    let (w, h) = (frame.width(), frame.height());
+    let wf = w as f64;
+    let hf = h as f64;
+
+    let x_start = (wf * 0.86) as u32;
+    let x_end = (wf * 0.99) as u32;
+    let y_start = (hf * 0.12) as u32;
+    let y_end = (hf * 0.75) as u32;
+    if x_start >= x_end || y_start >= y_end {
+        return None;
+    }
+
+    // Count highlight pixels per row
+    let row_counts: Vec<u32> = (y_start..y_end)
+        .map(|y| {
+            (x_start..x_end)
+                .filter(|&x| {
+                    let px = frame.get_pixel(x, y);
+                    px[0] > 170 && px[1] > 130 && px[2] < 90
+                })
+                .count() as u32
+        })
+        .collect();
+
+    let (best_offset, &best_count) = row_counts
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, &c)| c)?;
+
+    if best_count < 8 {
+        debug!("No highlighted scoreboard row found");
+        return None;
+    }
+
+    // Grow the band around the best row while rows still carry highlight pixels
+    let cutoff = best_count / 3;
+    let mut top = best_offset;
+    while top > 0 && row_counts[top - 1] > cutoff {
+        top -= 1;
+    }
+    let mut bottom = best_offset;
+    while bottom + 1 < row_counts.len() && row_counts[bottom + 1] > cutoff {
+        bottom += 1;
+    }
+
+    let band_top = y_start + top as u32;
+    let band_h = (bottom - top + 1) as u32;
+    let region_h = band_h.max(14);
+
+    debug!(
+        "Local player scoreboard row at y={}..{}",
+        band_top,
+        band_top + band_h
+    );
+
+    // Health sits in the rightmost third of the scoreboard entry
+    let region_x = x_start + (x_end - x_start) * 2 / 3;
+    Some(ScreenRegion {
+        x: region_x as f64 / wf,
+        y: band_top as f64 / hf,
+        width: (x_end - region_x) as f64 / wf,
+        height: region_h.min(h - band_top) as f64 / hf,
+    })
+}
+
 /// Detect the top of the game area (skipping any window titlebar).
 fn find_game_top(frame: &RgbaImage) -> u32 {
     let (w, h) = (frame.width(), frame.height());
@@ -1,12 +1,33 @@
+mod augment;
+mod bench;
+mod board;
+mod carousel;
 mod champion_matcher;
 mod digit_reader;
 pub mod game_area;
+mod item_matcher;
 pub mod layout;
+pub mod phase;
+mod placement;
+mod state;
+mod streak;
+mod template;
+mod traits;
 
+pub use augment::{detect_augment_cards, AugmentCard, AugmentMatcher};
+pub use bench::{read_bench, BenchUnit};
+pub use board::{read_board, BoardUnit};
+pub use carousel::{detect_carousel_units, CarouselUnit};
 pub use champion_matcher::{ChampionMatcher, MatchResult};
 pub use digit_reader::DigitReader;
 pub use game_area::{detect_game_area, GameArea};
+pub use item_matcher::ItemMatcher;
 pub use layout::{detect_layout, DetectedLayout};
+pub use phase::{classify_phase, GamePhase};
+pub use placement::read_placement;
+pub use state::to_game_state;
+pub use streak::{classify_streak, read_streak, StreakKind};
+pub use traits::{read_trait_panel, TraitMatcher, TraitReading, TraitStyle};
 
 use image::RgbaImage;
 use serde::{Deserialize, Serialize};
@@ -14,10 +35,30 @@ use serde::{Deserialize, Serialize};
 /// Combined result from the vision pipeline for a single frame
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct VisionResult {
+    pub phase: GamePhase,
     pub shop: Vec<ShopSlotResult>,
     pub gold: Option<u32>,
     pub level: Option<u32>,
     pub stage: Option<String>,
+    /// Local player's health from the scoreboard
+    pub hp: Option<u32>,
+    /// Signed streak: positive for wins, negative for losses, `None` when no flame is shown
+    pub streak: Option<i32>,
+    /// Offered augments, only filled while the selection overlay is showing
+    pub augments: Vec<AugmentCard>,
+    /// Rows of the trait tracker
+    pub traits: Vec<TraitReading>,
+    /// Recognised bench units
+    pub bench: Vec<BenchUnit>,
+    /// Recognised units on the local player's board
+    pub board: Vec<BoardUnit>,
+    /// Units on the carousel ring, only filled during carousel rounds
+    pub carousel: Vec<CarouselUnit>,
+    /// Final placement, only read on the post-game screen
+    pub placement: Option<u32>,
+    /// Position of the frame within the source video. `process_frame` has no
+    /// notion of time, so the caller fills this in.
+    pub timestamp_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +75,9 @@ pub struct ShopSlotResult {
 pub fn process_frame(
     frame: &RgbaImage,
     matcher: &ChampionMatcher,
+    augment_matcher: &AugmentMatcher,
+    trait_matcher: &TraitMatcher,
+    item_matcher: &ItemMatcher,
     digit_reader: &DigitReader,
 ) -> VisionResult {
     let (w, h) = (frame.width(), frame.height());
@@ -41,6 +85,45 @@ pub fn process_frame(
     // Dynamically detect UI layout
     let layout = detect_layout(frame);
 
+    let stage = layout.stage.as_ref().and_then(|r| {
+        let crop = tft_capture::crop_region(frame, r);
+        tracing::debug!("Stage crop: {}x{}", crop.width(), crop.height());
+        digit_reader.read_stage(&crop)
+    });
+
+    // Shop and economy reads are garbage outside planning/combat frames
+    let phase = classify_phase(frame, &layout);
+    if !phase.has_hud() {
+        tracing::debug!(
+            "Vision: {:?} frame, skipping shop and economy reads (stage={:?})",
+            phase,
+            stage
+        );
+        let augments = if phase == GamePhase::AugmentSelection {
+            detect_augment_cards(frame, augment_matcher, digit_reader)
+        } else {
+            Vec::new()
+        };
+        let placement = if phase == GamePhase::PostGame {
+            read_placement(frame, digit_reader)
+        } else {
+            None
+        };
+        let carousel = if phase == GamePhase::Carousel {
+            detect_carousel_units(frame, matcher, item_matcher)
+        } else {
+            Vec::new()
+        };
+        return VisionResult {
+            phase,
+            stage,
+            augments,
+            carousel,
+            placement,
+            ..Default::default()
+        };
+    }
+
     // Process shop slots from detected positions
     let mut shop = Vec::with_capacity(5);
     for (i, region) in layout.shop_slots.iter().enumerate() {
@@ -69,28 +152,54 @@ pub fn process_frame(
         digit_reader.read_number(&crop)
     });
 
-    let stage = layout.stage.as_ref().and_then(|r| {
+    let hp = layout.health.as_ref().and_then(|r| {
         let crop = tft_capture::crop_region(frame, r);
-        tracing::debug!("Stage crop: {}x{}", crop.width(), crop.height());
-        digit_reader.read_stage(&crop)
+        tracing::debug!("Health crop: {}x{}", crop.width(), crop.height());
+        digit_reader.read_number(&crop).filter(|&hp| hp <= 100)
     });
 
+    let streak = layout.streak.as_ref().and_then(|r| {
+        let crop = tft_capture::crop_region(frame, r);
+        tracing::debug!("Streak crop: {}x{}", crop.width(), crop.height());
+        read_streak(&crop, digit_reader)
+    });
+
+    let traits = read_trait_panel(frame, trait_matcher, digit_reader);
+    let bench = read_bench(frame, layout.hud_top, matcher);
+    let board = read_board(frame, layout.hud_top, matcher);
+
     tracing::debug!(
-        "Vision: {} shop slots, gold={:?}, level={:?}, stage={:?} (frame {}x{}, hud_top={:.1}%)",
+        "Vision: {:?}, {} shop slots, gold={:?}, level={:?}, stage={:?}, hp={:?}, streak={:?}, {} traits, {} bench units, {} board units (frame {}x{}, hud_top={:.1}%)",
+        phase,
         shop.len(),
         gold,
         level,
         stage,
+        hp,
+        streak,
+        traits.len(),
+        bench.len(),
+        board.len(),
         w,
         h,
         layout.hud_top * 100.0,
     );
 
     VisionResult {
+        phase,
         shop,
         gold,
         level,
         stage,
+        hp,
+        streak,
+        augments: Vec::new(),
+        traits,
+        bench,
+        board,
+        carousel: Vec::new(),
+        placement: None,
+        timestamp_ms: 0,
     }
 }
 
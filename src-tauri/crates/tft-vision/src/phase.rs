@@ -0,0 +1,224 @@
+use crate::layout::{find_bright_segments, smooth, DetectedLayout};
+use image::RgbaImage;
+use tracing::debug;
+
+pub use tft_state::GamePhase;
+
+/// Mean brightness below which a frame without a stage label is a loading screen
+const LOADING_MAX_BRIGHTNESS: f64 = 28.0;
+
+/// Share of banner-gold pixels in the center box that marks the placement screen
+const PLACEMENT_BANNER_RATIO: f64 = 0.08;
+
+/// Classify the game phase of a frame from HUD presence, the round timer,
+/// the stage label and the overall scene layout.
+pub fn classify_phase(frame: &RgbaImage, layout: &DetectedLayout) -> GamePhase {
+    let (w, h) = (frame.width(), frame.height());
+    if w < 100 || h < 100 {
+        return GamePhase::Unknown;
+    }
+
+    // The augment overlay dims the HUD underneath, so check it before HUD presence
+    let center_cards = count_center_cards(frame);
+    if center_cards == 3 {
+        debug!("Phase: three center cards → augment selection");
+        return GamePhase::AugmentSelection;
+    }
+
+    let hud_visible = layout.gold.is_some() && !layout.shop_slots.is_empty();
+    if hud_visible {
+        return match layout.stage.as_ref() {
+            Some(stage) if timer_is_combat(frame, stage) => GamePhase::Combat,
+            _ => GamePhase::Planning,
+        };
+    }
+
+    if has_placement_banner(frame) {
+        debug!("Phase: placement banner → post-game");
+        return GamePhase::PostGame;
+    }
+
+    if center_cards > 0 {
+        // After a pick the other cards fade out first
+        debug!(
+            "Phase: {} center cards without HUD → augment selection",
+            center_cards
+        );
+        return GamePhase::AugmentSelection;
+    }
+
+    if layout.stage.is_some() {
+        // Stage label without a shop only happens on the carousel
+        return GamePhase::Carousel;
+    }
+
+    let brightness = mean_brightness(frame);
+    debug!("Phase: no HUD or stage, mean brightness {:.1}", brightness);
+    if brightness < LOADING_MAX_BRIGHTNESS {
+        GamePhase::Loading
+    } else {
+        GamePhase::Unknown
+    }
+}
+
+/// Average brightness over a coarse grid of the whole frame.
+fn mean_brightness(frame: &RgbaImage) -> f64 {
+    let (w, h) = (frame.width(), frame.height());
+    let step_x = (w / 64).max(1);
+    let step_y = (h / 36).max(1);
+    let mut sum = 0.0;
+    let mut count = 0u32;
+    for y in (0..h).step_by(step_y as usize) {
+        for x in (0..w).step_by(step_x as usize) {
+            let px = frame.get_pixel(x, y);
+            sum += (px[0] as f64 + px[1] as f64 + px[2] as f64) / 3.0;
+            count += 1;
+        }
+    }
+    sum / count.max(1) as f64
+}
+
+/// Count large bright cards side by side across the middle of the frame.
+/// The augment overlay shows three tall cards, each 15-25% of the frame wide,
+/// against a darkened background.
+pub(crate) fn count_center_cards(frame: &RgbaImage) -> usize {
+    center_card_spans(frame).len()
+}
+
+/// Horizontal pixel spans of the large center cards (see `count_center_cards`).
+pub(crate) fn center_card_spans(frame: &RgbaImage) -> Vec<(usize, usize)> {
+    let (w, h) = (frame.width(), frame.height());
+    let band_top = h * 25 / 100;
+    let band_bottom = h * 70 / 100;
+    let y_step = ((band_bottom - band_top) / 20).max(1);
+
+    let profile: Vec<f64> = (0..w)
+        .map(|x| {
+            let mut sum = 0.0;
+            let mut cnt = 0u32;
+            let mut y = band_top;
+            while y < band_bottom {
+                let px = frame.get_pixel(x, y);
+                sum += (px[0] as f64 + px[1] as f64 + px[2] as f64) / 3.0;
+                cnt += 1;
+                y += y_step;
+            }
+            sum / cnt.max(1) as f64
+        })
+        .collect();
+
+    let smoothed = smooth(&profile, (w as usize / 200).max(3));
+
+    // The overlay background is dark; cards stand well above it
+    let mut sorted = smoothed.clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let dark_ref = sorted[sorted.len() / 10];
+    let bright_ref = sorted[sorted.len() * 9 / 10];
+    if bright_ref - dark_ref < 25.0 {
+        return Vec::new();
+    }
+    let threshold = dark_ref + (bright_ref - dark_ref) * 0.5;
+
+    let min_width = w as usize * 15 / 100;
+    let max_width = w as usize * 25 / 100;
+    find_bright_segments(&smoothed, threshold, min_width)
+        .into_iter()
+        .filter(|(s, e)| e - s <= max_width)
+        .collect()
+}
+
+/// The round timer bar sits just below the stage label and turns red while
+/// combat is running.
+fn timer_is_combat(frame: &RgbaImage, stage: &tft_capture::ScreenRegion) -> bool {
+    let (w, h) = (frame.width(), frame.height());
+    let y_start = ((stage.y + stage.height) * h as f64) as u32;
+    let y_end = (y_start + (h as f64 * 0.03) as u32).min(h);
+    let x_start = w * 35 / 100;
+    let x_end = w * 65 / 100;
+
+    let mut red = 0u32;
+    let mut colored = 0u32;
+    for y in y_start..y_end {
+        for x in x_start..x_end {
+            let px = frame.get_pixel(x, y);
+            let (r, g, b) = (px[0] as i32, px[1] as i32, px[2] as i32);
+            let max = r.max(g).max(b);
+            let min = r.min(g).min(b);
+            if max < 90 || max - min < 60 {
+                continue; // dark or unsaturated: not part of the bar fill
+            }
+            colored += 1;
+            if r == max && r - g > 70 && r - b > 70 {
+                red += 1;
+            }
+        }
+    }
+
+    debug!("Timer bar: {} colored pixels, {} red", colored, red);
+    colored > 20 && red * 2 > colored
+}
+
+/// Detect the large gold placement banner in the center of the post-game screen.
+pub(crate) fn has_placement_banner(frame: &RgbaImage) -> bool {
+    let (w, h) = (frame.width(), frame.height());
+    let x_start = w * 30 / 100;
+    let x_end = w * 70 / 100;
+    let y_start = h * 20 / 100;
+    let y_end = h * 45 / 100;
+
+    let mut gold = 0u32;
+    let mut total = 0u32;
+    for y in (y_start..y_end).step_by(2) {
+        for x in (x_start..x_end).step_by(2) {
+            let px = frame.get_pixel(x, y);
+            if px[0] > 180 && px[1] > 140 && px[2] < 100 {
+                gold += 1;
+            }
+            total += 1;
+        }
+    }
+
+    total > 0 && gold as f64 / total as f64 > PLACEMENT_BANNER_RATIO
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    fn empty_layout() -> DetectedLayout {
+        DetectedLayout {
+            shop_slots: Vec::new(),
+            gold: None,
+            level: None,
+            stage: None,
+            streak: None,
+            health: None,
+            hud_top: 0.8,
+        }
+    }
+
+    #[test]
+    fn test_dark_frame_is_loading() {
+        let frame = RgbaImage::from_pixel(320, 180, Rgba([10, 10, 12, 255]));
+        assert_eq!(classify_phase(&frame, &empty_layout()), GamePhase::Loading);
+    }
+
+    #[test]
+    fn test_three_cards_is_augment_selection() {
+        // Dark overlay with three bright cards, each 20% of the width
+        let frame = RgbaImage::from_fn(400, 200, |x, _| {
+            let on_card = (40..120).contains(&x) || (160..240).contains(&x) || (280..360).contains(&x);
+            if on_card {
+                Rgba([170, 160, 140, 255])
+            } else {
+                Rgba([20, 20, 25, 255])
+            }
+        });
+        assert_eq!(count_center_cards(&frame), 3);
+        assert_eq!(
+            classify_phase(&frame, &empty_layout()),
+            GamePhase::AugmentSelection
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use crate::DigitReader;
+use image::RgbaImage;
+use tracing::debug;
+
+/// Read the final placement (1-8) from the post-game banner.
+///
+/// The banner shows an ordinal ("1st", "4th", ...) in large text in the upper
+/// center of the screen. OCR is restricted to digits, so the suffix is dropped
+/// and only the leading digit is kept.
+pub fn read_placement(frame: &RgbaImage, digit_reader: &DigitReader) -> Option<u32> {
+    let (w, h) = (frame.width(), frame.height());
+    let banner =
+        image::imageops::crop_imm(frame, w * 40 / 100, h * 20 / 100, w * 20 / 100, h * 25 / 100)
+            .to_image();
+
+    let value = digit_reader.read_number(&banner)?;
+    let placement = leading_digit(value);
+    debug!("Placement banner OCR: {} → {:?}", value, placement);
+    placement
+}
+
+/// Keep the leading digit of an OCR'd ordinal, rejecting anything outside 1-8
+fn leading_digit(value: u32) -> Option<u32> {
+    let mut v = value;
+    while v >= 10 {
+        v /= 10;
+    }
+    (1..=8).contains(&v).then_some(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leading_digit() {
+        assert_eq!(leading_digit(4), Some(4));
+        // "1st" can come back as "15" when the "s" is read as a 5
+        assert_eq!(leading_digit(15), Some(1));
+        assert_eq!(leading_digit(0), None);
+        assert_eq!(leading_digit(9), None);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{TraitStyle, VisionResult};
+use tft_data::GameData;
+use tft_state::{
+    parse_stage, ActiveTrait, BoardSlot, CarouselOffer, Champion, GameState, OfferedAugment,
+    ShopSlot, TraitTier,
+};
+
+/// Build the per-frame part of a `GameState` from a vision result.
+///
+/// Champion names, costs and traits come from the static data when the
+/// champion is known there. History (rounds, picked augments, carousel picks)
+/// is not visible on a single frame and is left for the caller's trackers.
+pub fn to_game_state(vision: &VisionResult, data: &GameData) -> GameState {
+    let stage = vision.stage.clone().unwrap_or_default();
+    let round = parse_stage(&stage).map(|(_, round)| round).unwrap_or(0);
+
+    let shop = vision
+        .shop
+        .iter()
+        .map(|s| ShopSlot {
+            index: s.slot_index,
+            champion: s
+                .champion_id
+                .as_deref()
+                .map(|id| champion(data, id, s.champion_name.as_deref(), s.cost)),
+            confidence: s.confidence,
+        })
+        .collect();
+
+    let bench = vision
+        .bench
+        .iter()
+        .map(|b| BoardSlot {
+            row: 0,
+            col: b.slot as u32,
+            champion: Some(champion(data, &b.champion_id, Some(&b.champion_name), None)),
+            star_level: 1,
+            items: Vec::new(),
+        })
+        .collect();
+
+    let board = vision
+        .board
+        .iter()
+        .map(|b| BoardSlot {
+            row: b.row,
+            col: b.col,
+            champion: Some(champion(data, &b.champion_id, Some(&b.champion_name), None)),
+            star_level: 1,
+            items: Vec::new(),
+        })
+        .collect();
+
+    let augment_offer = vision
+        .augments
+        .iter()
+        .map(|a| OfferedAugment {
+            slot: a.slot,
+            augment_id: a.augment_id.clone(),
+            name: a.name.clone(),
+            highlighted: a.highlighted,
+        })
+        .collect();
+
+    let carousel = vision
+        .carousel
+        .iter()
+        .map(|u| CarouselOffer {
+            champion_id: u.champion_id.clone(),
+            item_id: u.item_id.clone(),
+        })
+        .collect();
+
+    GameState {
+        phase: vision.phase,
+        gold: vision.gold.unwrap_or(0),
+        level: vision.level.unwrap_or(0),
+        stage,
+        round,
+        shop,
+        bench,
+        board,
+        hp: vision.hp.unwrap_or(0),
+        streak: vision.streak.unwrap_or(0),
+        augment_offer,
+        carousel,
+        traits: active_traits(vision),
+        placement: vision.placement,
+        ..Default::default()
+    }
+}
+
+/// Look up a champion in the static data, falling back to what the matcher reported.
+fn champion(data: &GameData, id: &str, name: Option<&str>, cost: Option<u32>) -> Champion {
+    match data.champions.get(id) {
+        Some(c) => Champion {
+            id: c.id.clone(),
+            name: c.name.trim().to_string(),
+            cost: c.cost,
+            traits: c.traits.clone(),
+        },
+        None => Champion {
+            id: id.to_string(),
+            name: name.unwrap_or(id).trim().to_string(),
+            cost: cost.unwrap_or(0),
+            traits: Vec::new(),
+        },
+    }
+}
+
+/// Active traits from the trait tracker rows that were fully recognised
+fn active_traits(vision: &VisionResult) -> Vec<ActiveTrait> {
+    vision
+        .traits
+        .iter()
+        .filter_map(|t| {
+            let tier = match t.style {
+                TraitStyle::Inactive => return None,
+                TraitStyle::Bronze => TraitTier::Bronze,
+                TraitStyle::Silver => TraitTier::Silver,
+                TraitStyle::Gold => TraitTier::Gold,
+                TraitStyle::Prismatic => TraitTier::Prismatic,
+            };
+            Some(ActiveTrait {
+                trait_id: t.trait_id.clone()?,
+                name: t.name.clone()?,
+                count: t.count?,
+                tier,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoardUnit, ShopSlotResult, TraitReading};
+    use tft_data::ChampionData;
+
+    #[test]
+    fn test_to_game_state_looks_up_champions() {
+        let mut data = GameData::default();
+        data.champions.insert(
+            "TFT16_Aatrox".to_string(),
+            ChampionData {
+                id: "TFT16_Aatrox".to_string(),
+                name: "Aatrox ".to_string(),
+                cost: 5,
+                traits: vec!["TFT16_Darkin".to_string()],
+                icon: String::new(),
+                range: 1,
+            },
+        );
+
+        let vision = VisionResult {
+            gold: Some(32),
+            stage: Some("3-2".to_string()),
+            shop: vec![
+                ShopSlotResult {
+                    slot_index: 0,
+                    champion_id: Some("TFT16_Aatrox".to_string()),
+                    champion_name: Some("Aatrox".to_string()),
+                    cost: Some(5),
+                    confidence: 0.8,
+                },
+                ShopSlotResult {
+                    slot_index: 1,
+                    champion_id: None,
+                    champion_name: None,
+                    cost: None,
+                    confidence: 0.0,
+                },
+            ],
+            traits: vec![TraitReading {
+                trait_id: Some("TFT16_Darkin".to_string()),
+                name: Some("Darkin".to_string()),
+                count: Some(1),
+                next_breakpoint: Some(2),
+                style: TraitStyle::Inactive,
+                confidence: 0.9,
+            }],
+            board: vec![BoardUnit {
+                row: 3,
+                col: 6,
+                champion_id: "TFT16_Aatrox".to_string(),
+                champion_name: "Aatrox".to_string(),
+                confidence: 0.7,
+            }],
+            ..Default::default()
+        };
+
+        let state = to_game_state(&vision, &data);
+        assert_eq!(state.gold, 32);
+        assert_eq!(state.round, 2);
+        let aatrox = state.shop[0].champion.as_ref().unwrap();
+        assert_eq!(aatrox.name, "Aatrox");
+        assert_eq!(aatrox.traits, vec!["TFT16_Darkin".to_string()]);
+        assert!(state.shop[1].champion.is_none());
+        assert_eq!((state.board[0].row, state.board[0].col), (3, 6));
+        assert_eq!(state.board[0].champion.as_ref().unwrap().cost, 5);
+        // Inactive trait rows are not active traits
+        assert!(state.traits.is_empty());
+    }
+}
@@ -0,0 +1,90 @@
+use crate::DigitReader;
+use image::{Rgba, RgbaImage};
+use tracing::debug;
+
+/// Minimum share of flame pixels in the indicator crop to trust the color
+const MIN_FLAME_RATIO: f64 = 0.04;
+
+/// Which way the player's streak is running, judged by the flame color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreakKind {
+    Win,
+    Loss,
+}
+
+/// Orange-red flame pixel used by the win streak indicator
+pub(crate) fn is_fire_pixel(px: &Rgba<u8>) -> bool {
+    let (r, g, b) = (px[0] as i32, px[1] as i32, px[2] as i32);
+    r > 200 && (70..190).contains(&g) && b < 80 && r - g > 50
+}
+
+/// Blue flame pixel used by the loss streak indicator
+pub(crate) fn is_ice_pixel(px: &Rgba<u8>) -> bool {
+    let (r, g, b) = (px[0] as i32, px[1] as i32, px[2] as i32);
+    b > 180 && g > 110 && r < 120 && b - r > 80
+}
+
+/// Classify the streak indicator crop by its dominant flame color.
+pub fn classify_streak(crop: &RgbaImage) -> Option<StreakKind> {
+    let total = (crop.width() * crop.height()) as f64;
+    if total == 0.0 {
+        return None;
+    }
+
+    let fire = crop.pixels().filter(|p| is_fire_pixel(p)).count() as f64;
+    let ice = crop.pixels().filter(|p| is_ice_pixel(p)).count() as f64;
+
+    debug!(
+        "Streak flame: fire={:.1}%, ice={:.1}%",
+        fire / total * 100.0,
+        ice / total * 100.0
+    );
+
+    if fire.max(ice) / total < MIN_FLAME_RATIO {
+        None
+    } else if fire >= ice {
+        Some(StreakKind::Win)
+    } else {
+        Some(StreakKind::Loss)
+    }
+}
+
+/// Read the signed streak from the indicator crop: positive for a win streak,
+/// negative for a loss streak.
+pub fn read_streak(crop: &RgbaImage, digit_reader: &DigitReader) -> Option<i32> {
+    let kind = classify_streak(crop)?;
+
+    // The counter is printed to the right of the flame; OCR only that half
+    let (w, h) = crop.dimensions();
+    let digits_x = w * 45 / 100;
+    let digits = image::imageops::crop_imm(crop, digits_x, 0, (w - digits_x).max(1), h).to_image();
+    let count = digit_reader.read_number(&digits)? as i32;
+
+    // Streaks are capped in-game and start at 2; anything else is a misread
+    if !(2..=20).contains(&count) {
+        debug!("Discarding implausible streak count {}", count);
+        return None;
+    }
+
+    Some(match kind {
+        StreakKind::Win => count,
+        StreakKind::Loss => -count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_streak_colors() {
+        let fire = RgbaImage::from_pixel(20, 12, Rgba([240, 120, 30, 255]));
+        assert_eq!(classify_streak(&fire), Some(StreakKind::Win));
+
+        let ice = RgbaImage::from_pixel(20, 12, Rgba([60, 160, 230, 255]));
+        assert_eq!(classify_streak(&ice), Some(StreakKind::Loss));
+
+        let plain = RgbaImage::from_pixel(20, 12, Rgba([30, 30, 30, 255]));
+        assert_eq!(classify_streak(&plain), None);
+    }
+}
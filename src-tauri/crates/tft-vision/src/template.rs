@@ -0,0 +1,103 @@
+use crate::champion_matcher::{compute_stats, normalized_cross_correlation, MATCH_SIZE};
+use anyhow::{Context, Result};
+use image::{GrayImage, RgbaImage};
+use std::path::Path;
+
+/// Crops this uniform are empty slots, not icons
+const MIN_ICON_STD_DEV: f64 = 5.0;
+
+/// Pre-processed icon template (augment, trait or item) for matching
+pub(crate) struct IconTemplate {
+    pub id: String,
+    pub name: String,
+    gray: GrayImage,
+    /// Pre-computed mean and std for NCC
+    mean: f64,
+    std_dev: f64,
+}
+
+impl IconTemplate {
+    /// Load an icon image and resize it to the matching size
+    pub fn load(path: &Path, id: &str, name: &str) -> Result<Self> {
+        let img =
+            image::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let (gray, mean, std_dev) = prepare(&img.to_luma8());
+        Ok(Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            gray,
+            mean,
+            std_dev,
+        })
+    }
+}
+
+/// Match an icon crop against `templates`. Returns (id, name, confidence) of
+/// the best match scoring at least `min_confidence`.
+pub(crate) fn match_icon(
+    templates: &[IconTemplate],
+    icon: &RgbaImage,
+    min_confidence: f64,
+) -> Option<(String, String, f64)> {
+    let (resized, mean, std_dev) = prepare(&image::imageops::grayscale(icon));
+    if std_dev < MIN_ICON_STD_DEV {
+        return None;
+    }
+
+    templates
+        .iter()
+        .map(|t| {
+            let score =
+                normalized_cross_correlation(&resized, mean, std_dev, &t.gray, t.mean, t.std_dev);
+            (t, score)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .filter(|(_, score)| *score >= min_confidence)
+        .map(|(t, score)| (t.id.clone(), t.name.clone(), score))
+}
+
+fn prepare(gray: &GrayImage) -> (GrayImage, f64, f64) {
+    let resized = image::imageops::resize(
+        gray,
+        MATCH_SIZE,
+        MATCH_SIZE,
+        image::imageops::FilterType::Triangle,
+    );
+    let (mean, std_dev) = compute_stats(&resized);
+    (resized, mean, std_dev)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_icon_picks_best_template() {
+        let icon = |seed: u32| {
+            RgbaImage::from_fn(32, 32, |x, y| {
+                let v = ((x * seed + y * 13) % 256) as u8;
+                image::Rgba([v, v, v, 255])
+            })
+        };
+        let templates: Vec<IconTemplate> = [("A", 7), ("B", 29)]
+            .into_iter()
+            .map(|(id, seed)| {
+                let (gray, mean, std_dev) = prepare(&image::imageops::grayscale(&icon(seed)));
+                IconTemplate {
+                    id: id.to_string(),
+                    name: id.to_string(),
+                    gray,
+                    mean,
+                    std_dev,
+                }
+            })
+            .collect();
+
+        let (id, _, score) = match_icon(&templates, &icon(29), 0.5).unwrap();
+        assert_eq!(id, "B");
+        assert!(score > 0.99);
+        // Uniform crops are empty slots
+        let empty = RgbaImage::from_pixel(32, 32, image::Rgba([40, 40, 40, 255]));
+        assert!(match_icon(&templates, &empty, 0.5).is_none());
+    }
+}
@@ -0,0 +1,251 @@
+use crate::template::{match_icon, IconTemplate};
+use crate::DigitReader;
+use anyhow::Result;
+use image::{Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::{debug, info, warn};
+
+/// Minimum NCC score for a trait icon match
+const MIN_ICON_CONFIDENCE: f64 = 0.45;
+
+/// Share of a scanline inside the hexagon column that must carry hexagon color
+const MIN_ROW_FILL: f64 = 0.3;
+
+/// Tier color of a trait hexagon in the tracker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TraitStyle {
+    /// Grey hexagon: units present but no breakpoint reached
+    Inactive,
+    Bronze,
+    Silver,
+    Gold,
+    Prismatic,
+}
+
+/// One row of the trait tracker on the left side of the screen
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraitReading {
+    pub trait_id: Option<String>,
+    pub name: Option<String>,
+    /// Units counted towards the trait
+    pub count: Option<u32>,
+    /// Next breakpoint, when the tracker shows one ("3/5")
+    pub next_breakpoint: Option<u32>,
+    pub style: TraitStyle,
+    pub confidence: f64,
+}
+
+/// Matches trait tracker icons against trait icon templates
+pub struct TraitMatcher {
+    templates: Vec<IconTemplate>,
+}
+
+impl TraitMatcher {
+    /// Load trait icon templates from the data directory. Fails when there is
+    /// no trait data to match against.
+    /// Expects:
+    ///   - data_dir/traits.json (trait metadata)
+    ///   - data_dir/templates/traits/{icon} (icon images)
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let game_data = tft_data::GameData::load(data_dir)?;
+        if game_data.traits.is_empty() {
+            anyhow::bail!(
+                "No traits in {}. Run scripts/fetch-templates.py",
+                data_dir.display()
+            );
+        }
+        let templates_dir = data_dir.join("templates").join("traits");
+
+        let mut templates = Vec::new();
+        for (id, t) in &game_data.traits {
+            let icon_path = templates_dir.join(&t.icon);
+            if !icon_path.exists() {
+                debug!("Missing icon for trait {}: {}", id, icon_path.display());
+                continue;
+            }
+            match IconTemplate::load(&icon_path, id, &t.name) {
+                Ok(template) => templates.push(template),
+                Err(e) => warn!("Failed to load trait template for {}: {}", id, e),
+            }
+        }
+
+        info!(
+            "TraitMatcher loaded {} templates from {}",
+            templates.len(),
+            templates_dir.display()
+        );
+
+        Ok(Self { templates })
+    }
+
+    /// Match a hexagon crop against all trait icons.
+    /// Trait icons are drawn as a light glyph on the tier color, so matching is
+    /// done on grayscale structure and is independent of the tier.
+    pub fn match_icon(&self, icon: &RgbaImage) -> Option<(String, String, f64)> {
+        match_icon(&self.templates, icon, MIN_ICON_CONFIDENCE)
+    }
+
+    /// A matcher without templates, for when trait tracking is disabled
+    pub fn empty() -> Self {
+        Self {
+            templates: Vec::new(),
+        }
+    }
+
+    /// Number of loaded templates
+    pub fn template_count(&self) -> usize {
+        self.templates.len()
+    }
+}
+
+/// Read every row of the trait tracker: icon, unit count and tier color.
+pub fn read_trait_panel(
+    frame: &RgbaImage,
+    matcher: &TraitMatcher,
+    digit_reader: &DigitReader,
+) -> Vec<TraitReading> {
+    let w = frame.width();
+    let hex_x0 = w / 100;
+    let hex_x1 = (w * 35 / 1000).max(hex_x0 + 1);
+
+    let rows = find_hexagon_rows(frame, hex_x0, hex_x1);
+    debug!("Trait tracker: {} hexagon row(s)", rows.len());
+
+    rows.into_iter()
+        .map(|(y0, y1, style)| {
+            let row_h = y1 - y0;
+            let icon = image::imageops::crop_imm(frame, hex_x0, y0, hex_x1 - hex_x0, row_h).to_image();
+            let recognised = matcher.match_icon(&icon);
+
+            // The unit counter is printed right next to the hexagon
+            let count_w = (w * 25 / 1000).max(1);
+            let counter = image::imageops::crop_imm(frame, hex_x1, y0, count_w, row_h).to_image();
+            let ratio = digit_reader.read_ratio(&counter);
+
+            debug!(
+                "Trait row y={}..{}: {:?} {:?} count={:?}",
+                y0,
+                y1,
+                style,
+                recognised.as_ref().map(|r| &r.1),
+                ratio
+            );
+
+            TraitReading {
+                trait_id: recognised.as_ref().map(|r| r.0.clone()),
+                name: recognised.as_ref().map(|r| r.1.clone()),
+                count: ratio.map(|r| r.0),
+                next_breakpoint: ratio.and_then(|r| r.1),
+                style,
+                confidence: recognised.as_ref().map(|r| r.2).unwrap_or(0.0),
+            }
+        })
+        .collect()
+}
+
+/// Hexagon styles indexed by their discriminant, for per-style vote counting
+const STYLES: [TraitStyle; 5] = [
+    TraitStyle::Inactive,
+    TraitStyle::Bronze,
+    TraitStyle::Silver,
+    TraitStyle::Gold,
+    TraitStyle::Prismatic,
+];
+
+/// Classify a pixel as one of the hexagon tier colors.
+fn hexagon_style(px: &Rgba<u8>) -> Option<TraitStyle> {
+    let (r, g, b) = (px[0] as i32, px[1] as i32, px[2] as i32);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+
+    if r > 200 && g > 160 && b < 100 {
+        Some(TraitStyle::Gold)
+    } else if (r > 180 && b > 180 && g < 170) || (g > 180 && b > 180 && r < 170) {
+        Some(TraitStyle::Prismatic)
+    } else if (140..=215).contains(&r) && (80..=140).contains(&g) && b < 100 && r - b > 60 {
+        Some(TraitStyle::Bronze)
+    } else if min >= 150 && max <= 215 && max - min < 30 {
+        Some(TraitStyle::Silver)
+    } else if max > 35 && max < 90 && max - min < 20 {
+        Some(TraitStyle::Inactive)
+    } else {
+        None
+    }
+}
+
+/// Find the vertical extent and tier color of each hexagon in the tracker column.
+fn find_hexagon_rows(frame: &RgbaImage, x0: u32, x1: u32) -> Vec<(u32, u32, TraitStyle)> {
+    let h = frame.height();
+    let y_start = h * 15 / 100;
+    let y_end = h * 75 / 100;
+    let min_height = (h * 15 / 1000).max(3);
+    let span = (x1 - x0) as f64;
+
+    // Per scanline: dominant hexagon style, if enough of the line carries it
+    let line_styles: Vec<Option<TraitStyle>> = (y_start..y_end)
+        .map(|y| {
+            let mut counts = [0u32; 5];
+            for x in x0..x1 {
+                if let Some(style) = hexagon_style(frame.get_pixel(x, y)) {
+                    counts[style as usize] += 1;
+                }
+            }
+            let (idx, &best) = counts.iter().enumerate().max_by_key(|(_, &c)| c)?;
+            if (best as f64) < span * MIN_ROW_FILL {
+                return None;
+            }
+            Some(STYLES[idx])
+        })
+        .collect();
+
+    // Group consecutive styled lines into hexagons
+    let mut rows = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut votes = [0u32; 5];
+    for (i, style) in line_styles.iter().chain(std::iter::once(&None)).enumerate() {
+        match (style, start) {
+            (Some(s), None) => {
+                start = Some(i);
+                votes = [0; 5];
+                votes[*s as usize] += 1;
+            }
+            (Some(s), Some(_)) => votes[*s as usize] += 1,
+            (None, Some(s0)) => {
+                if (i - s0) as u32 >= min_height {
+                    let (idx, _) = votes.iter().enumerate().max_by_key(|(_, &c)| c).unwrap();
+                    rows.push((y_start + s0 as u32, y_start + i as u32, STYLES[idx]));
+                }
+                start = None;
+            }
+            (None, None) => {}
+        }
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_hexagon_rows_styles() {
+        // Dark panel with a gold hexagon and a bronze hexagon in the tracker column
+        let frame = RgbaImage::from_fn(400, 400, |x, y| {
+            if x < 14 {
+                match y {
+                    80..=99 => Rgba([230, 190, 60, 255]),
+                    110..=129 => Rgba([180, 110, 60, 255]),
+                    _ => Rgba([10, 10, 10, 255]),
+                }
+            } else {
+                Rgba([10, 10, 10, 255])
+            }
+        });
+        let rows = find_hexagon_rows(&frame, 4, 14);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], (80, 100, TraitStyle::Gold));
+        assert_eq!(rows[1].2, TraitStyle::Bronze);
+    }
+}
@@ -0,0 +1,54 @@
+use super::capture::{resolve_data_dir, PipelineState};
+use std::sync::OnceLock;
+use tauri::State;
+use tft_advisor::{roll_odds, Advice, RollOdds, RollQuery, RollSetup};
+use tft_data::GameData;
+
+/// Game data for commands, loaded on first use
+#[derive(Default)]
+pub struct GameDataState(OnceLock<GameData>);
+
+impl GameDataState {
+    fn get(&self, app_handle: &tauri::AppHandle) -> Result<&GameData, String> {
+        if let Some(data) = self.0.get() {
+            return Ok(data);
+        }
+        let data = GameData::load(&resolve_data_dir(app_handle)).map_err(|e| e.to_string())?;
+        Ok(self.0.get_or_init(|| data))
+    }
+}
+
+/// Latest advice for the current game state
+#[tauri::command]
+pub fn get_advice(pipeline_state: State<'_, PipelineState>) -> Result<Option<Advice>, String> {
+    let pipeline = pipeline_state.0.lock().map_err(|e| e.to_string())?;
+    Ok(pipeline.as_ref().and_then(|p| p.latest_advice()))
+}
+
+/// Odds of hitting the wanted copies of a unit by rolling down the given gold.
+/// Remaining copies come from the query, else from the live pool estimate for
+/// `championId`, else a full pool is assumed.
+#[tauri::command]
+pub fn calculate_roll_odds(
+    app_handle: tauri::AppHandle,
+    query: RollQuery,
+    pipeline_state: State<'_, PipelineState>,
+    game_data: State<'_, GameDataState>,
+) -> Result<RollOdds, String> {
+    let game_data = game_data.get(&app_handle)?;
+
+    let remaining = query.remaining.or_else(|| {
+        let id = query.champion_id.as_deref()?;
+        let pipeline = pipeline_state.0.lock().ok()?;
+        let state = pipeline.as_ref()?.latest_state()?;
+        state
+            .pool
+            .iter()
+            .find(|e| e.champion_id == id)
+            .map(|e| e.remaining)
+    });
+
+    let setup = RollSetup::from_data(game_data, query.level, query.cost, remaining)
+        .map_err(|e| e.to_string())?;
+    Ok(roll_odds(&setup, query.copies_wanted, query.gold))
+}
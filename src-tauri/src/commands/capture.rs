@@ -2,11 +2,12 @@ use crate::pipeline::Pipeline;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use tauri::{Manager, State};
+use tft_state::GameState;
 
 pub struct PipelineState(pub Mutex<Option<Pipeline>>);
 
 /// Resolve the data directory for vision assets
-fn resolve_data_dir(app_handle: &tauri::AppHandle) -> PathBuf {
+pub(crate) fn resolve_data_dir(app_handle: &tauri::AppHandle) -> PathBuf {
     app_handle
         .path()
         .resource_dir()
@@ -65,26 +66,9 @@ pub fn get_capture_status(
 #[tauri::command]
 pub fn get_game_state(
     pipeline_state: State<'_, PipelineState>,
-) -> Result<serde_json::Value, String> {
+) -> Result<Option<GameState>, String> {
     let pipeline = pipeline_state.0.lock().map_err(|e| e.to_string())?;
-    match &*pipeline {
-        Some(p) => match p.latest_vision() {
-            Some(vision) => Ok(serde_json::json!({
-                "shop": vision.shop.iter().map(|s| serde_json::json!({
-                    "index": s.slot_index,
-                    "championId": s.champion_id,
-                    "championName": s.champion_name,
-                    "cost": s.cost,
-                    "confidence": s.confidence,
-                })).collect::<Vec<_>>(),
-                "gold": vision.gold,
-                "level": vision.level,
-                "stage": vision.stage,
-            })),
-            None => Ok(serde_json::json!(null)),
-        },
-        None => Ok(serde_json::json!(null)),
-    }
+    Ok(pipeline.as_ref().and_then(|p| p.latest_state()))
 }
 
 /// Save the current frame and region crops for debugging.
@@ -1,3 +1,5 @@
+mod advisor;
 mod capture;
 
+pub use advisor::*;
 pub use capture::*;
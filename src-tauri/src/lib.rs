@@ -1,7 +1,7 @@
 mod commands;
 mod pipeline;
 
-use commands::PipelineState;
+use commands::{GameDataState, PipelineState};
 use std::sync::Mutex;
 
 pub fn run() {
@@ -16,12 +16,15 @@ pub fn run() {
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .manage(PipelineState(Mutex::new(None)))
+        .manage(GameDataState::default())
         .invoke_handler(tauri::generate_handler![
             commands::stop_capture,
             commands::get_capture_status,
             commands::get_game_state,
             commands::save_debug_frame,
             commands::start_video_analysis,
+            commands::get_advice,
+            commands::calculate_roll_odds,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -1,37 +1,61 @@
+use anyhow::Context;
 use image::RgbaImage;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tauri::{AppHandle, Emitter};
 use tokio::sync::watch;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 
+use tft_advisor::{
+    review_match, Advice, AdviceManager, AdviceRecord, AdvisorRegistry, ReviewReport, RuleAdvisor,
+};
+#[cfg(feature = "llm")]
+use tft_advisor::{LlmConfig, LlmInsight};
 use tft_capture::CaptureStatus;
-use tft_vision::{ChampionMatcher, DigitReader, VisionResult};
+use tft_data::GameData;
+use tft_state::{
+    parse_stage, AugmentTracker, CarouselTracker, EventDetector, GamePhase, GameState,
+    MatchRecorder, PoolTracker, Readings, RoundTracker, TemporalFilter, TimelineEntry,
+};
+use tft_vision::{
+    AugmentMatcher, ChampionMatcher, DigitReader, ItemMatcher, TraitMatcher, VisionResult,
+};
 
 /// Manages the capture → CV → state → advice pipeline
 pub struct Pipeline {
     stop: Arc<AtomicBool>,
     frame_rx: watch::Receiver<Option<Arc<RgbaImage>>>,
     status_rx: watch::Receiver<CaptureStatus>,
-    vision_rx: watch::Receiver<Option<VisionResult>>,
+    state_rx: watch::Receiver<Option<GameState>>,
+    advice_rx: watch::Receiver<Option<Advice>>,
 }
 
 impl Pipeline {
     /// Spawn the downstream tasks shared by both capture and video sources:
-    /// vision processing loop, status emitter, and game-state emitter.
+    /// vision processing loop, status emitter, and game-state and advice emitter.
+    /// When `output_dir` is set, the timeline and match summary are saved there
+    /// once the source ends. Returns the game-state and advice channels.
     fn spawn_downstream(
         app_handle: &AppHandle,
         frame_rx: &watch::Receiver<Option<Arc<RgbaImage>>>,
         status_rx: &watch::Receiver<CaptureStatus>,
-        vision_tx: watch::Sender<Option<VisionResult>>,
-        vision_rx: &watch::Receiver<Option<VisionResult>>,
         stop: &Arc<AtomicBool>,
         data_dir: PathBuf,
+        output_dir: Option<PathBuf>,
+    ) -> (
+        watch::Receiver<Option<GameState>>,
+        watch::Receiver<Option<Advice>>,
     ) {
+        let (vision_tx, mut vision_rx) = watch::channel::<Option<VisionResult>>(None);
+        let (state_tx, state_rx) = watch::channel::<Option<GameState>>(None);
+        let (advice_tx, advice_rx) = watch::channel::<Option<Advice>>(None);
+        let state_data_dir = data_dir.clone();
+
         // Vision processing loop
         let mut vision_frame_rx = frame_rx.clone();
+        let vision_status_rx = status_rx.clone();
         let stop_vision = stop.clone();
         tauri::async_runtime::spawn(async move {
             let data_dir_clone = data_dir.clone();
@@ -44,21 +68,47 @@ impl Pipeline {
                     ChampionMatcher::load(&PathBuf::from("/dev/null"))
                         .unwrap_or_else(|_| panic!("Failed to create empty matcher"))
                 });
+                let augment_matcher = AugmentMatcher::load(&data_dir_clone).unwrap_or_else(|e| {
+                    warn!(
+                        "Failed to load augment matcher: {}. Augment recognition disabled.",
+                        e
+                    );
+                    AugmentMatcher::load(&PathBuf::from("/dev/null"))
+                        .unwrap_or_else(|_| panic!("Failed to create empty augment matcher"))
+                });
+                let trait_matcher = TraitMatcher::load(&data_dir_clone).unwrap_or_else(|e| {
+                    warn!("Failed to load trait matcher: {:#}. Trait tracking disabled.", e);
+                    TraitMatcher::empty()
+                });
+                let item_matcher = ItemMatcher::load(&data_dir_clone).unwrap_or_else(|e| {
+                    warn!("Failed to load item matcher: {}. Item recognition disabled.", e);
+                    ItemMatcher::load(&PathBuf::from("/dev/null"))
+                        .unwrap_or_else(|_| panic!("Failed to create empty item matcher"))
+                });
                 let digit_reader = DigitReader::new();
                 info!(
-                    "Vision pipeline ready: {} templates, OCR {}",
+                    "Vision pipeline ready: {} templates, {} augments, {} traits, {} items, OCR {}",
                     matcher.template_count(),
+                    augment_matcher.augment_count(),
+                    trait_matcher.template_count(),
+                    item_matcher.template_count(),
                     if digit_reader.is_available() {
                         "enabled"
                     } else {
                         "disabled"
                     }
                 );
-                (Arc::new(matcher), Arc::new(digit_reader))
+                (
+                    Arc::new(matcher),
+                    Arc::new(augment_matcher),
+                    Arc::new(trait_matcher),
+                    Arc::new(item_matcher),
+                    Arc::new(digit_reader),
+                )
             })
             .await;
 
-            let (matcher, digit_reader) = match init {
+            let (matcher, augment_matcher, trait_matcher, item_matcher, digit_reader) = match init {
                 Ok(v) => v,
                 Err(e) => {
                     warn!("Failed to initialize vision: {}", e);
@@ -76,8 +126,12 @@ impl Pipeline {
                 }
 
                 let frame = vision_frame_rx.borrow().clone();
+                let position_ms = vision_status_rx.borrow().position_ms;
                 if let Some(frame) = frame {
                     let m = matcher.clone();
+                    let am = augment_matcher.clone();
+                    let tm = trait_matcher.clone();
+                    let im = item_matcher.clone();
                     let dr = digit_reader.clone();
                     let result = tokio::task::spawn_blocking(move || {
                         // Detect game area within the frame, then crop before vision
@@ -90,7 +144,7 @@ impl Pipeline {
                             } else {
                                 tft_capture::crop_region(&frame, &game_area.region)
                             };
-                            tft_vision::process_frame(&cropped, &m, &dr)
+                            tft_vision::process_frame(&cropped, &m, &am, &tm, &im, &dr)
                         } else {
                             // No game area detected — emit empty result
                             VisionResult::default()
@@ -98,7 +152,8 @@ impl Pipeline {
                     })
                     .await;
 
-                    if let Ok(vision_result) = result {
+                    if let Ok(mut vision_result) = result {
+                        vision_result.timestamp_ms = position_ms.unwrap_or(0);
                         let _ = vision_tx.send(Some(vision_result));
                     }
                 }
@@ -126,32 +181,199 @@ impl Pipeline {
             }
         });
 
-        // Emit vision results as game state to frontend
-        let mut vision_rx_clone = vision_rx.clone();
+        // Build game state from vision results, track history, run the advisor
+        // and emit both to frontend
         let app_clone2 = app_handle.clone();
         tauri::async_runtime::spawn(async move {
+            let game_data = GameData::load(&state_data_dir).unwrap_or_else(|e| {
+                warn!("Failed to load game data: {}. Champion details disabled.", e);
+                GameData::default()
+            });
+            let mut filter = TemporalFilter::default();
+            let mut rounds = RoundTracker::new();
+            let mut augments = AugmentTracker::new();
+            let mut carousels = CarouselTracker::new();
+            let mut detector = EventDetector::with_recipes(game_data.item_recipes());
+            let mut pool = PoolTracker::new(game_data.champion_pool());
+            let mut recorder = MatchRecorder::new();
+            let mut advisors = AdvisorRegistry::default();
+            advisors.register(Box::new(RuleAdvisor::new(&state_data_dir)));
+            #[cfg(feature = "llm")]
+            let llm = LlmConfig::from_env().map(LlmInsight::new);
+            let mut manager = AdviceManager::new();
+            let mut advice_log: Vec<Advice> = Vec::new();
+            // State at the start of each round, for the review at the end
+            let mut round_states: Vec<GameState> = Vec::new();
+            // Last state the advisors ran on, and what they advised
+            let mut last_state: Option<GameState> = None;
+            let mut fresh = Advice::default();
             loop {
-                if vision_rx_clone.changed().await.is_err() {
+                if vision_rx.changed().await.is_err() {
                     break;
                 }
-                let result = vision_rx_clone.borrow().clone();
-                if let Some(vision) = result {
-                    let payload = serde_json::json!({
-                        "shop": vision.shop.iter().map(|s| serde_json::json!({
-                            "index": s.slot_index,
-                            "championId": s.champion_id,
-                            "championName": s.champion_name,
-                            "cost": s.cost,
-                            "confidence": s.confidence,
-                        })).collect::<Vec<_>>(),
-                        "gold": vision.gold,
-                        "level": vision.level,
-                        "stage": vision.stage,
-                    });
-                    let _ = app_clone2.emit("game-state", payload);
+                let result = vision_rx.borrow().clone();
+                let Some(vision) = result else {
+                    continue;
+                };
+
+                let mut state = tft_vision::to_game_state(&vision, &game_data);
+                let smoothed = filter.update(Readings {
+                    gold: vision.gold,
+                    level: vision.level,
+                    stage: vision.stage.clone(),
+                    shop: std::mem::take(&mut state.shop),
+                });
+                apply_smoothed(&mut state, &smoothed);
+
+                if let Some(record) = rounds.observe(
+                    smoothed.stage.as_deref(),
+                    vision.phase.has_hud(),
+                    vision.hp,
+                    vision.streak,
+                ) {
+                    info!(
+                        "Round {} ended: {:?}, {} damage taken",
+                        record.stage, record.outcome, record.damage_taken
+                    );
+                }
+                augments.observe(&state.augment_offer);
+                let bench: Vec<String> = state
+                    .bench
+                    .iter()
+                    .filter_map(|b| b.champion.as_ref().map(|c| c.id.clone()))
+                    .collect();
+                if let Some(pick) = carousels.observe(
+                    state.phase == GamePhase::Carousel,
+                    smoothed.stage.as_deref(),
+                    &state.carousel,
+                    &bench,
+                    vision.timestamp_ms,
+                ) {
+                    recorder.record_carousel(pick.clone());
+                }
+                recorder.record(TimelineEntry {
+                    timestamp_ms: vision.timestamp_ms,
+                    stage: smoothed.stage.clone(),
+                    gold: smoothed.gold,
+                    level: smoothed.level,
+                    hp: vision.hp,
+                });
+                recorder.record_board(&state.board);
+                if let Some(placement) = vision.placement {
+                    recorder.record_placement(vision.timestamp_ms, placement);
+                }
+
+                // History and last known values from the trackers
+                if let Some(hp) = rounds.hp() {
+                    state.hp = hp;
+                }
+                state.streak = rounds.streak();
+                state.rounds = rounds.history().to_vec();
+                state.augments = augments.chosen().to_vec();
+                state.carousels = carousels.picks().to_vec();
+
+                pool.observe(&state);
+                let held = state.bench.iter().chain(&state.board).map(|s| &s.champion);
+                let offered = state.shop.iter().map(|s| &s.champion);
+                // Scouted units tell the advisors what the lobby plays
+                state.pool = pool.estimates_for(
+                    held.chain(offered)
+                        .flatten()
+                        .map(|c| c.id.as_str())
+                        .chain(pool.scouted()),
+                );
+
+                let events = detector.observe(&state, vision.timestamp_ms);
+                for event in &events {
+                    let _ = app_clone2.emit("game-event", event);
+                }
+                recorder.record_events(&events);
+
+                // Advisors only see states that differ from the last one; an
+                // unchanged state still feeds the manager while an econ switch
+                // waits for its confirming updates
+                let state_changed = !events.is_empty()
+                    || last_state.as_ref().is_none_or(|last| !last.same_content(&state));
+                if !state_changed && !manager.has_pending() {
+                    continue;
+                }
+                if state_changed {
+                    let _ = app_clone2.emit("game-state", &state);
+
+                    let new_round = round_states.last().is_none_or(|s| s.stage != state.stage);
+                    if new_round && !state.stage.is_empty() && state.level > 0 {
+                        round_states.push(state.clone());
+                    }
+
+                    fresh = advisors.advise(&state, &game_data);
+                    #[cfg(feature = "llm")]
+                    if let Some(llm) = &llm {
+                        fresh.llm_insight = llm.insight(&state, &fresh);
+                    }
+                }
+                let mut advice = fresh.clone();
+                advice.timestamp = vision.timestamp_ms;
+                // Judge the advice on screen by what the player just did, then
+                // let the manager decide what to show next
+                manager.observe_events(&events);
+                let advice = manager
+                    .update(&state, advice, vision.timestamp_ms)
+                    .clone();
+                // Only emit when the advice itself changed, not just its
+                // timestamp or the jittery inputs in its traces
+                let changed = match advice_tx.borrow().as_ref() {
+                    Some(last) => !last.same_content(&advice),
+                    None => true,
+                };
+                if changed {
+                    if let Some(econ) = &advice.econ {
+                        debug!("Econ advice {:?}: {}", econ.action, econ.trace.summary());
+                    }
+                    // Only kept for the match files written at the end
+                    if output_dir.is_some() {
+                        advice_log.push(advice.clone());
+                    }
+                    let _ = app_clone2.emit("advice", &advice);
+                    let _ = advice_tx.send(Some(advice));
+                }
+
+                if state_changed {
+                    last_state = Some(state.clone());
+                    let _ = state_tx.send(Some(state));
                 }
             }
+
+            // Source ended (or was stopped): wrap up the match
+            if recorder.timeline().is_empty() {
+                return;
+            }
+            let summary = recorder.summary(augments.chosen(), rounds.history());
+            info!(
+                "Match finished: placement {:?}, final stage {:?}, {} rounds",
+                summary.placement,
+                summary.final_stage,
+                summary.rounds.len()
+            );
+            let _ = app_clone2.emit("match-summary", &summary);
+            if let Some(dir) = output_dir {
+                let review = review_match(
+                    &round_states,
+                    recorder.ledger().rounds(),
+                    &advisors,
+                    &game_data,
+                );
+                save_match(
+                    &dir,
+                    &recorder,
+                    &summary,
+                    &advice_log,
+                    manager.records(),
+                    &review,
+                );
+            }
         });
+
+        (state_rx, advice_rx)
     }
 
     /// Start the pipeline with video file analysis
@@ -165,11 +387,11 @@ impl Pipeline {
 
         let (frame_tx, frame_rx) = watch::channel::<Option<Arc<RgbaImage>>>(None);
         let (status_tx, status_rx) = watch::channel(CaptureStatus::default());
-        let (vision_tx, vision_rx) = watch::channel::<Option<VisionResult>>(None);
 
         let frame_interval = Duration::from_millis(frame_interval_ms);
 
         // Start video decode loop
+        let video_path_out = video_path.clone();
         let stop_clone = stop.clone();
         tauri::async_runtime::spawn(async move {
             if let Err(e) =
@@ -179,14 +401,13 @@ impl Pipeline {
             }
         });
 
-        Self::spawn_downstream(
+        let (state_rx, advice_rx) = Self::spawn_downstream(
             &app_handle,
             &frame_rx,
             &status_rx,
-            vision_tx,
-            &vision_rx,
             &stop,
             data_dir,
+            Some(analysis_dir(&video_path_out)),
         );
 
         info!("Pipeline started (video analysis)");
@@ -195,7 +416,8 @@ impl Pipeline {
             stop,
             frame_rx,
             status_rx,
-            vision_rx,
+            state_rx,
+            advice_rx,
         }
     }
 
@@ -208,8 +430,14 @@ impl Pipeline {
         self.status_rx.borrow().clone()
     }
 
-    pub fn latest_vision(&self) -> Option<VisionResult> {
-        self.vision_rx.borrow().clone()
+    /// Latest game state built from the vision results
+    pub fn latest_state(&self) -> Option<GameState> {
+        self.state_rx.borrow().clone()
+    }
+
+    /// Latest advice for the game state
+    pub fn latest_advice(&self) -> Option<Advice> {
+        self.advice_rx.borrow().clone()
     }
 
     /// Get the latest captured frame
@@ -283,6 +511,14 @@ impl Pipeline {
             let crop = tft_capture::crop_region(&game_frame, r);
             let _ = crop.save(debug_dir.join("stage.png"));
         }
+        if let Some(ref r) = layout.streak {
+            let crop = tft_capture::crop_region(&game_frame, r);
+            let _ = crop.save(debug_dir.join("streak.png"));
+        }
+        if let Some(ref r) = layout.health {
+            let crop = tft_capture::crop_region(&game_frame, r);
+            let _ = crop.save(debug_dir.join("health.png"));
+        }
 
         // Save detected layout info
         let mut info = format!("Frame: {}x{}\nGame area: {}x{}\n", w, h, gw, gh);
@@ -322,3 +558,67 @@ impl Pipeline {
         Some(debug_dir)
     }
 }
+
+/// Directory for analysis output next to a video: `<dir>/<stem>_analysis/`
+fn analysis_dir(video_path: &Path) -> PathBuf {
+    let stem = video_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "video".to_string());
+    video_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(format!("{}_analysis", stem))
+}
+
+/// Write timeline.json, summary.json, advice.json (every advice change, with
+/// its traces), advice_records.json (whether each recommendation was
+/// followed) and review.json/review.md for a finished match
+fn save_match(
+    dir: &Path,
+    recorder: &MatchRecorder,
+    summary: &tft_state::MatchSummary,
+    advice: &[Advice],
+    records: &[AdviceRecord],
+    review: &ReviewReport,
+) {
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        warn!("Failed to create {}: {}", dir.display(), e);
+        return;
+    }
+    if let Err(e) = recorder.timeline().save(&dir.join("timeline.json")) {
+        warn!("Failed to save timeline: {}", e);
+    }
+    if let Err(e) = save_json(&dir.join("advice.json"), advice) {
+        warn!("Failed to save advice: {}", e);
+    }
+    if let Err(e) = save_json(&dir.join("advice_records.json"), records) {
+        warn!("Failed to save advice records: {}", e);
+    }
+    match review.save(dir) {
+        Ok(()) => info!(
+            "Advisor review: {} of {} rounds went against the advice",
+            review.disagreeing().count(),
+            review.rounds.len()
+        ),
+        Err(e) => warn!("Failed to save advisor review: {}", e),
+    }
+    match summary.save(&dir.join("summary.json")) {
+        Ok(()) => info!("Match summary saved to {}", dir.display()),
+        Err(e) => warn!("Failed to save match summary: {}", e),
+    }
+}
+
+fn save_json<T: serde::Serialize + ?Sized>(path: &Path, value: &T) -> anyhow::Result<()> {
+    let json = serde_json::to_string_pretty(value)?;
+    std::fs::write(path, json).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Put the filter's committed values into a freshly converted state.
+fn apply_smoothed(state: &mut GameState, smoothed: &Readings) {
+    state.gold = smoothed.gold.unwrap_or(0);
+    state.level = smoothed.level.unwrap_or(0);
+    state.stage = smoothed.stage.clone().unwrap_or_default();
+    state.round = parse_stage(&state.stage).map(|(_, round)| round).unwrap_or(0);
+    state.shop = smoothed.shop.clone();
+}
@@ -1,23 +1,12 @@
 import { useEffect } from "react";
+import { useAdvice } from "./useAdvice";
 import { useGameState } from "./useGameState";
-import type { CaptureStatus, ShopSlot } from "../types";
-
-interface GameStatePayload {
-  shop: Array<{
-    index: number;
-    championId: string | null;
-    championName: string | null;
-    cost: number | null;
-    confidence: number;
-  }>;
-  gold: number | null;
-  level: number | null;
-  stage: string | null;
-}
+import type { Advice, CaptureStatus, GameState } from "../types";
 
 export function useCaptureEvents() {
   const setCaptureStatus = useGameState((s) => s.setCaptureStatus);
   const setGameState = useGameState((s) => s.setGameState);
+  const setAdvice = useAdvice((s) => s.setAdvice);
 
   useEffect(() => {
     const unlisteners: Array<() => void> = [];
@@ -31,30 +20,21 @@ export function useCaptureEvents() {
         });
         unlisteners.push(u1);
 
-        const u2 = await listen<GameStatePayload>("game-state", (event) => {
-          const payload = event.payload;
-
-          const shop: ShopSlot[] = payload.shop.map((s) => ({
-            index: s.index,
-            champion: s.championId
-              ? {
-                  id: s.championId,
-                  name: s.championName ?? s.championId,
-                  cost: s.cost ?? 0,
-                  traits: [],
-                }
-              : null,
-            confidence: s.confidence,
-          }));
-
+        const u2 = await listen<GameState>("game-state", (event) => {
+          // Stage and level stay empty until their first stable read
+          const { stage, level, ...rest } = event.payload;
           setGameState({
-            shop,
-            ...(payload.gold != null ? { gold: payload.gold } : {}),
-            ...(payload.level != null ? { level: payload.level } : {}),
-            ...(payload.stage != null ? { stage: payload.stage } : {}),
+            ...rest,
+            ...(stage ? { stage } : {}),
+            ...(level ? { level } : {}),
           });
         });
         unlisteners.push(u2);
+
+        const u3 = await listen<Advice>("advice", (event) => {
+          setAdvice(event.payload);
+        });
+        unlisteners.push(u3);
       } catch {
         // Not running in Tauri
       }
@@ -65,5 +45,5 @@ export function useCaptureEvents() {
     return () => {
       unlisteners.forEach((u) => u());
     };
-  }, [setCaptureStatus, setGameState]);
+  }, [setCaptureStatus, setGameState, setAdvice]);
 }
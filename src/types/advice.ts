@@ -1,9 +1,35 @@
+export interface TraceInput {
+  name: string;
+  value: unknown;
+}
+
+export interface TraceCheck {
+  input: string;
+  op: string;
+  threshold: unknown;
+  passed: boolean;
+}
+
+export interface TraceAlternative {
+  option: string;
+  score: number;
+}
+
+/** Why a piece of advice was given */
+export interface AdviceTrace {
+  rule: string;
+  inputs: TraceInput[];
+  checks: TraceCheck[];
+  alternatives: TraceAlternative[];
+}
+
 export type ShopAction = "buy" | "skip" | "consider";
 
 export interface ShopAdvice {
   slotIndex: number;
   action: ShopAction;
   reason: string;
+  trace?: AdviceTrace;
 }
 
 export type EconAction = "level" | "roll" | "save" | "slow-roll";
@@ -12,6 +38,7 @@ export interface EconAdvice {
   action: EconAction;
   reason: string;
   targetGold?: number;
+  trace?: AdviceTrace;
 }
 
 export interface ItemAdvice {
@@ -20,6 +47,7 @@ export interface ItemAdvice {
   result: string;
   priority: number;
   reason: string;
+  trace?: AdviceTrace;
 }
 
 export interface CompAdvice {
@@ -27,6 +55,30 @@ export interface CompAdvice {
   matchScore: number;
   missingUnits: string[];
   coreItems: string[];
+  trace?: AdviceTrace;
+}
+
+/** A board hex; row 0 is the front row, row 3 the back row */
+export interface Hex {
+  row: number;
+  col: number;
+}
+
+export interface PositioningAdvice {
+  champion: string;
+  hex: Hex;
+  moveTo: Hex | null;
+  reason: string;
+  trace?: AdviceTrace;
+}
+
+export interface AugmentAdvice {
+  slot: number;
+  augmentId: string;
+  name: string;
+  score: number;
+  reasons: string[];
+  trace?: AdviceTrace;
 }
 
 export interface Advice {
@@ -34,6 +86,10 @@ export interface Advice {
   econ: EconAdvice | null;
   items: ItemAdvice[];
   comp: CompAdvice | null;
+  comps: CompAdvice[];
+  positioning: PositioningAdvice[];
+  /** Offered augments, best first */
+  augments: AugmentAdvice[];
   llmInsight: string | null;
   timestamp: number;
 }
@@ -43,6 +99,24 @@ export const DEFAULT_ADVICE: Advice = {
   econ: null,
   items: [],
   comp: null,
+  comps: [],
+  positioning: [],
+  augments: [],
   llmInsight: null,
   timestamp: 0,
 };
+
+export interface RollQuery {
+  level: number;
+  cost: number;
+  copiesWanted: number;
+  gold: number;
+  championId?: string;
+  remaining?: number;
+}
+
+export interface RollOdds {
+  probability: number;
+  expectedGold: number | null;
+  maxRolls: number;
+}
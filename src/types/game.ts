@@ -25,17 +25,97 @@ export interface BoardSlot {
   items: ItemSlot[];
 }
 
+export type GamePhase =
+  | "Unknown"
+  | "Planning"
+  | "Combat"
+  | "Carousel"
+  | "AugmentSelection"
+  | "Loading"
+  | "PostGame";
+
+export interface RoundRecord {
+  stage: string;
+  outcome: "Win" | "Loss" | "Unknown";
+  hpBefore: number | null;
+  hpAfter: number | null;
+  damageTaken: number;
+  streak: number;
+}
+
+export interface OfferedAugment {
+  slot: number;
+  augmentId: string | null;
+  name: string | null;
+  highlighted: boolean;
+}
+
+export interface CarouselOffer {
+  championId: string;
+  itemId: string | null;
+}
+
+export interface CarouselPick {
+  stage: string | null;
+  championId: string;
+  itemId: string | null;
+  timestampMs: number;
+}
+
+export interface ActiveTrait {
+  traitId: string;
+  name: string;
+  count: number;
+  tier: "Bronze" | "Silver" | "Gold" | "Prismatic";
+}
+
+export interface PoolEstimate {
+  championId: string;
+  total: number;
+  owned: number;
+  scouted: number;
+  remaining: number;
+}
+
 export interface GameState {
+  phase: GamePhase;
   gold: number;
   level: number;
   stage: string;
+  round: number;
   shop: ShopSlot[];
   bench: BoardSlot[];
   board: BoardSlot[];
   items: ItemSlot[];
-  round: number;
+  hp: number;
+  streak: number;
+  rounds: RoundRecord[];
+  augments: string[];
+  augmentOffer: OfferedAugment[];
+  carousel: CarouselOffer[];
+  carousels: CarouselPick[];
+  traits: ActiveTrait[];
+  pool: PoolEstimate[];
+  placement: number | null;
 }
 
+export type GameEventKind =
+  | { type: "championBought"; championId: string; cost: number }
+  | { type: "championSold"; championId: string }
+  | { type: "shopRerolled" }
+  | { type: "xpPurchased" }
+  | { type: "levelUp"; level: number }
+  | { type: "starUp"; championId: string; starLevel: number }
+  | { type: "roundStarted"; round: string }
+  | { type: "roundEnded"; round: string; outcome: RoundRecord["outcome"] | null }
+  | { type: "itemCombined"; itemId: string; components: string[] }
+  | { type: "interestGained"; amount: number };
+
+export type GameEvent = GameEventKind & {
+  timestampMs: number;
+  stage: string;
+};
+
 export interface CaptureStatus {
   isCapturing: boolean;
   windowFound: boolean;
@@ -46,14 +126,25 @@ export interface CaptureStatus {
 }
 
 export const DEFAULT_GAME_STATE: GameState = {
+  phase: "Unknown",
   gold: 0,
   level: 1,
   stage: "1-1",
+  round: 0,
   shop: [],
   bench: [],
   board: [],
   items: [],
-  round: 0,
+  hp: 100,
+  streak: 0,
+  rounds: [],
+  augments: [],
+  augmentOffer: [],
+  carousel: [],
+  carousels: [],
+  traits: [],
+  pool: [],
+  placement: null,
 };
 
 export const DEFAULT_CAPTURE_STATUS: CaptureStatus = {